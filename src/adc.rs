@@ -1,9 +1,15 @@
 use crate::crm::{Enable, Reset};
+use crate::dma::{
+    self,
+    traits::{DMASet, PeriAddress},
+    PeripheralToMemory, Transfer,
+};
 use crate::{
     gpio::{self, Analog},
     pac,
 };
 use core::fmt;
+use core::sync::atomic::{compiler_fence, Ordering};
 
 mod f4;
 
@@ -87,6 +93,21 @@ pub mod config {
         }
     }
 
+    /// The place in the preempted sequence a given channel should be converted
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+    #[repr(u8)]
+    pub enum PreemptedSequence {
+        /// 1
+        One = 0,
+        /// 2
+        Two = 1,
+        /// 3
+        Three = 2,
+        /// 4
+        Four = 3,
+    }
+
     /// The number of cycles to sample a given channel for
     #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[derive(Copy, Clone, PartialEq, Eq, Debug)]
@@ -256,6 +277,87 @@ pub mod config {
         }
     }
 
+    /// Possible triggers of the preempted sequence
+    #[cfg(not(any(
+        feature = "at32f402",
+        feature = "at32f405",
+        feature = "at32f423",
+        feature = "at32f435",
+        feature = "at32f437",
+    )))]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    #[repr(u8)]
+    pub enum PreemptedTrigger {
+        /// TIM1 trigger out
+        Tim_1_trgo = 0b000,
+        /// TIM1 compare channel 4
+        Tim_1_cc_4 = 0b001,
+        /// TIM2 trigger out
+        Tim_2_trgo = 0b010,
+        /// TIM2 compare channel 1
+        Tim_2_cc_1 = 0b011,
+        /// TIM3 compare channel 4
+        Tim_3_cc_4 = 0b100,
+        /// TIM4 trigger out
+        Tim_4_trgo = 0b101,
+        /// External interrupt line 15
+        Exti_15 = 0b110,
+        /// Software trigger, see `start_preempted_conversion`
+        Software = 0b111,
+    }
+
+    /// Possible triggers of the preempted sequence
+    #[cfg(any(
+        feature = "at32f402",
+        feature = "at32f405",
+        feature = "at32f423",
+        feature = "at32f435",
+        feature = "at32f437",
+    ))]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    #[repr(u8)]
+    pub enum PreemptedTrigger {
+        /// TIM1 compare channel 4
+        Tim_1_cc_4 = 0b0000,
+        /// TIM1 trigger out
+        Tim_1_trgo = 0b0001,
+        /// TIM2 compare channel 1
+        Tim_2_cc_1 = 0b0010,
+        /// TIM2 trigger out
+        Tim_2_trgo = 0b0011,
+        /// TIM3 compare channel 2
+        Tim_3_cc_2 = 0b0100,
+        /// TIM3 compare channel 4
+        Tim_3_cc_4 = 0b0101,
+        /// TIM4 compare channel 1
+        Tim_4_cc_1 = 0b0110,
+        /// TIM4 compare channel 2
+        Tim_4_cc_2 = 0b0111,
+        /// TIM4 compare channel 3
+        Tim_4_cc_3 = 0b1000,
+        /// TIM4 trigger out
+        Tim_4_trgo = 0b1001,
+        /// TIM5 compare channel 4
+        Tim_5_cc_4 = 0b1010,
+        /// TIM5 trigger out
+        Tim_5_trgo = 0b1011,
+        /// TIM8 compare channel 2
+        Tim_8_cc_2 = 0b1100,
+        /// TIM8 compare channel 3
+        Tim_8_cc_3 = 0b1101,
+        /// TIM8 compare channel 4
+        Tim_8_cc_4 = 0b1110,
+        /// External interrupt line 15
+        Exti_15 = 0b1111,
+    }
+    impl From<PreemptedTrigger> for u8 {
+        fn from(pt: PreemptedTrigger) -> u8 {
+            pt as _
+        }
+    }
+
     /// Possible trigger modes
     #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[derive(Copy, Clone, PartialEq, Eq, Debug)]
@@ -264,6 +366,24 @@ pub mod config {
         Disabled = 0,
         /// Listen for rising edges of external trigger
         RisingEdge = 1,
+        /// Listen for falling edges of external trigger
+        #[cfg(any(
+            feature = "at32f402",
+            feature = "at32f405",
+            feature = "at32f423",
+            feature = "at32f435",
+            feature = "at32f437",
+        ))]
+        FallingEdge = 2,
+        /// Listen for both edges of external trigger
+        #[cfg(any(
+            feature = "at32f402",
+            feature = "at32f405",
+            feature = "at32f423",
+            feature = "at32f435",
+            feature = "at32f437",
+        ))]
+        BothEdges = 3,
     }
     impl From<TriggerMode> for bool {
         fn from(tm: TriggerMode) -> bool {
@@ -337,6 +457,24 @@ pub mod config {
         Continuous,
     }
 
+    /// How `Adc::scan` fills its buffer
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum ScanMode {
+        /// Buffer is filled once, then the DMA requests stop
+        Single,
+        /// DMA wraps around at the end of the buffer and keeps writing
+        Circular,
+    }
+    impl From<ScanMode> for Dma {
+        fn from(mode: ScanMode) -> Dma {
+            match mode {
+                ScanMode::Single => Dma::Single,
+                ScanMode::Circular => Dma::Continuous,
+            }
+        }
+    }
+
     /// End-of-conversion interrupt enabled/disabled
     #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[derive(Copy, Clone, PartialEq, Eq, Debug)]
@@ -468,6 +606,106 @@ impl<ADC> fmt::Debug for Adc<ADC> {
     }
 }
 
+/// Half of a circular scan buffer
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Half {
+    /// Written before the half transfer event
+    First,
+    /// Written before the transfer complete event
+    Second,
+}
+
+/// Regular sequence converted into a buffer by DMA
+///
+/// Created by `Adc::scan` from the channels set up with `configure_channel`. Samples are
+/// stored in sequence order, sequence after sequence:
+///
+/// ```rust
+/// static mut SAMPLES: [u16; 64] = [0; 64];
+///
+/// let mut adc = Adc::adc1(dp.ADC1, true, AdcConfig::default().continuous(Continuous::Continuous));
+/// adc.configure_channel(&pa0, Sequence::One, SampleTime::Cycles_28);
+/// adc.configure_channel(&pa1, Sequence::Two, SampleTime::Cycles_28);
+/// let mut scan = adc.scan(
+///     dma1.1,
+///     unsafe { &mut *core::ptr::addr_of_mut!(SAMPLES) },
+///     ScanMode::Circular,
+/// );
+/// scan.listen(dma::Event::HalfTransfer | dma::Event::TransferComplete);
+/// scan.start();
+///
+/// // in the DMA channel interrupt, both flags are set if it was served late
+/// if scan.is_complete() {
+///     scan.clear_events(dma::Event::TransferComplete);
+///     process(scan.half(Half::Second));
+/// }
+/// if scan.is_half_complete() {
+///     scan.clear_events(dma::Event::HalfTransfer);
+///     process(scan.half(Half::First));
+/// }
+/// ```
+///
+/// With [`config::ScanMode::Single`] the buffer is filled once, with
+/// [`config::ScanMode::Circular`] the DMA wraps around and the halves must be read before
+/// they are overwritten. Without continuous mode or an external trigger every sequence is
+/// started by [`Scan::trigger`].
+pub struct Scan<ADC, DMA, const N: u8>
+where
+    Adc<ADC>: PeriAddress<MemSize = u16> + DMASet<PeripheralToMemory>,
+    DMA: dma::traits::Instance,
+{
+    transfer: Transfer<dma::Channel<DMA, N>, Adc<ADC>, &'static mut [u16], PeripheralToMemory>,
+    len: usize,
+    /// Configuration restored on release
+    config: config::AdcConfig,
+}
+
+impl<ADC, DMA, const N: u8> Scan<ADC, DMA, N>
+where
+    Adc<ADC>: PeriAddress<MemSize = u16> + DMASet<PeripheralToMemory>,
+    DMA: dma::traits::Instance,
+{
+    /// Returns `true` once the first half of the buffer has been written
+    pub fn is_half_complete(&self) -> bool {
+        self.transfer.is_half_complete()
+    }
+
+    /// Returns `true` once the end of the buffer has been written
+    pub fn is_complete(&self) -> bool {
+        self.transfer.is_complete()
+    }
+
+    /// Clear the flags of `event`, usually from the DMA channel interrupt
+    pub fn clear_events(&mut self, event: dma::Event) {
+        self.transfer.clear_events(event);
+    }
+
+    /// Enable DMA interrupts for `event`
+    pub fn listen(&mut self, event: dma::Event) {
+        self.transfer.listen(event);
+    }
+
+    /// Disable DMA interrupts for `event`
+    pub fn unlisten(&mut self, event: dma::Event) {
+        self.transfer.unlisten(event);
+    }
+
+    /// Samples of one half of the buffer
+    ///
+    /// The DMA keeps writing the other half meanwhile.
+    pub fn half(&self, half: Half) -> &[u16] {
+        let mid = self.len / 2;
+        let (start, len) = match half {
+            Half::First => (0, mid),
+            Half::Second => (mid, self.len - mid),
+        };
+        let buf = self.transfer.buffer_ptr() as *const u16;
+        compiler_fence(Ordering::Acquire);
+        unsafe { core::slice::from_raw_parts(buf.add(start), len) }
+    }
+}
+
 macro_rules! adc {
     // Note that only ADC1 supports measurement of VREF, VBAT, and the internal temperature sensor.
     (additionals: ADC1 => ($common_type:ident)) => {
@@ -597,8 +835,8 @@ macro_rules! adc {
                     self.set_align(config.align);
                     self.set_sequence(config.sequence);
                     // self.set_external_trigger(config.external_trigger);
-                    // self.set_continuous(config.continuous);
-                    // self.set_dma(config.dma);
+                    self.set_continuous(config.continuous);
+                    self.set_dma(config.dma);
                     self.set_end_of_conversion_interrupt(config.end_of_conversion_interrupt);
                     // self.set_default_sample_time(config.default_sample_time);
 
@@ -724,6 +962,27 @@ macro_rules! adc {
                     self.adc_reg.ctrl1().modify(|_, w| w.sqen().bit(sequence.into()));
                 }
 
+                /// Enables and disables continuous mode, which restarts the sequence when it ends
+                pub fn set_continuous(&mut self, continuous: config::Continuous) {
+                    self.config.continuous = continuous;
+                    self.adc_reg.ctrl2().modify(|_, w| w.rpen().bit(continuous.into()));
+                }
+
+                /// Sets the DMA requests of the regular sequence
+                pub fn set_dma(&mut self, dma: config::Dma) {
+                    self.config.dma = dma;
+                    self.adc_reg.ctrl2().modify(|_, w| w.ocdmaen().bit(dma != config::Dma::Disabled));
+                    #[cfg(any(
+                        feature = "at32f402",
+                        feature = "at32f405",
+                        feature = "at32f423",
+                        feature = "at32f435",
+                        feature = "at32f437",
+                    ))]
+                    // OCDRCEN keeps requesting DMA after the last transfer
+                    self.adc_reg.ctrl2().modify(|_, w| w.ocdrcen().bit(dma == config::Dma::Continuous));
+                }
+
                 /// Sets if the end-of-conversion behaviour.
                 /// The end-of-conversion interrupt occur either per conversion or for the whole sequence.
                 pub fn set_end_of_conversion_interrupt(&mut self, eoc: bool) {
@@ -790,6 +1049,11 @@ macro_rules! adc {
                         config::Sequence::Sixteen  => self.adc_reg.osq1().modify(|_, w| unsafe {w.osn16().bits(channel) }),
                     }
 
+                    self.set_sample_time(channel, sample_time);
+                }
+
+                /// Sets the sample time of `channel`, for regular and preempted conversions
+                fn set_sample_time(&mut self, channel: u8, sample_time: config::SampleTime) {
                     fn replace_bits(mut v: u32, offset: u32, width: u32, value: u32) -> u32 {
                         let mask = !(((1 << width) -1) << (offset * width));
                         v &= mask;
@@ -807,6 +1071,225 @@ macro_rules! adc {
                     }
                 }
 
+                /// Configure a channel for preempted conversions.
+                /// Preempted conversions interrupt the regular sequence and store their results
+                /// in separate registers, see [`preempted_sample`](Self::preempted_sample).
+                /// It will make sure the preempted sequence is at least as long as the `sequence` provided.
+                /// Sequence mode must be enabled to convert more than one preempted channel.
+                /// # Arguments
+                /// * `channel` - channel to configure
+                /// * `sequence` - where in the preempted sequence to convert the channel
+                /// * `sample_time` - how long to sample for, shared with regular conversions of the channel
+                /// * `offset` - subtracted from the result, which can become negative
+                pub fn configure_preempted_channel<CHANNEL>(
+                    &mut self,
+                    _channel: &CHANNEL,
+                    sequence: config::PreemptedSequence,
+                    sample_time: config::SampleTime,
+                    offset: u16,
+                )
+                where
+                    CHANNEL: Channel<pac::$adc_type, ID=u8>
+                {
+                    let channel = CHANNEL::channel();
+                    let rank = sequence as usize;
+
+                    // PCLEN is the sequence length - 1. Shorter sequences end at PSN4,
+                    // so the previous channels move down when it grows.
+                    self.adc_reg.psq().modify(|r, w| {
+                        let prev = r.pclen().bits() as usize;
+                        let len = prev.max(rank);
+                        let mut slots = [
+                            r.psn1().bits(),
+                            r.psn2().bits(),
+                            r.psn3().bits(),
+                            r.psn4().bits(),
+                        ];
+                        slots.rotate_left(len - prev);
+                        slots[4 - (len - prev)..].fill(0);
+                        slots[3 - len + rank] = channel;
+                        unsafe {
+                            w.pclen().bits(len as u8)
+                                .psn1().bits(slots[0])
+                                .psn2().bits(slots[1])
+                                .psn3().bits(slots[2])
+                                .psn4().bits(slots[3])
+                        }
+                    });
+
+                    // PCDTOx holds the 12-bit offset of the x-th conversion
+                    let offset = (offset & 0xfff).into();
+                    match sequence {
+                        config::PreemptedSequence::One   => self.adc_reg.pcdto1().write(|w| unsafe { w.bits(offset) }),
+                        config::PreemptedSequence::Two   => self.adc_reg.pcdto2().write(|w| unsafe { w.bits(offset) }),
+                        config::PreemptedSequence::Three => self.adc_reg.pcdto3().write(|w| unsafe { w.bits(offset) }),
+                        config::PreemptedSequence::Four  => self.adc_reg.pcdto4().write(|w| unsafe { w.bits(offset) }),
+                    };
+
+                    self.set_sample_time(channel, sample_time);
+                }
+
+                /// Reset the preempted sequence to a single conversion
+                pub fn reset_preempted_sequence(&mut self) {
+                    self.adc_reg.psq().reset();
+                }
+
+                #[cfg(not(any(
+                    feature = "at32f402",
+                    feature = "at32f405",
+                    feature = "at32f423",
+                    feature = "at32f435",
+                    feature = "at32f437",
+                )))]
+                /// Sets the trigger of the preempted sequence
+                pub fn set_preempted_trigger(
+                    &mut self,
+                    mode: config::TriggerMode,
+                    trigger: config::PreemptedTrigger,
+                ) {
+                    // PCTESEL is split into 3 low bits and 1 high bit
+                    let trigger: u8 = trigger.into();
+                    self.adc_reg.ctrl2().modify(|_, w| unsafe {
+                        w.pctesel_l().bits(trigger & 0b111)
+                            .pctesel_h().bit(trigger >> 3 != 0)
+                            .pcten().bit(mode.into())
+                    });
+                }
+
+                #[cfg(any(
+                    feature = "at32f402",
+                    feature = "at32f405",
+                    feature = "at32f423",
+                    feature = "at32f435",
+                    feature = "at32f437",
+                ))]
+                /// Sets the trigger and the active edge of the preempted sequence
+                pub fn set_preempted_trigger(
+                    &mut self,
+                    mode: config::TriggerMode,
+                    trigger: config::PreemptedTrigger,
+                ) {
+                    // PCTESEL is split into 4 low bits and 1 high bit
+                    let trigger: u8 = trigger.into();
+                    self.adc_reg.ctrl2().modify(|_, w| unsafe {
+                        w.pctesel_l().bits(trigger & 0b1111)
+                            .pctesel_h().bit(trigger >> 4 != 0)
+                            .pcten().bits(mode as u8)
+                    });
+                }
+
+                #[cfg(not(any(
+                    feature = "at32f402",
+                    feature = "at32f405",
+                    feature = "at32f423",
+                    feature = "at32f435",
+                    feature = "at32f437",
+                )))]
+                /// Starts conversion of the preempted sequence by software.
+                /// This replaces any hardware trigger, set it again with [`Self::set_preempted_trigger`].
+                pub fn start_preempted_conversion(&mut self) {
+                    self.enable();
+                    self.clear_preempted_end_of_conversion_flag();
+                    // The software trigger is selected like a hardware one
+                    self.set_preempted_trigger(
+                        config::TriggerMode::RisingEdge,
+                        config::PreemptedTrigger::Software,
+                    );
+                    self.adc_reg.ctrl2().modify(|_, w| w.pcswtrg().set_bit());
+                }
+
+                #[cfg(any(
+                    feature = "at32f402",
+                    feature = "at32f405",
+                    feature = "at32f423",
+                    feature = "at32f435",
+                    feature = "at32f437",
+                ))]
+                /// Starts conversion of the preempted sequence by software.
+                /// This replaces any hardware trigger, set it again with [`Self::set_preempted_trigger`].
+                pub fn start_preempted_conversion(&mut self) {
+                    self.enable();
+                    self.clear_preempted_end_of_conversion_flag();
+                    // The software trigger only works while the hardware trigger is disabled
+                    self.adc_reg.ctrl2().modify(|_, w| unsafe {
+                        w.pcten().bits(config::TriggerMode::Disabled as u8)
+                            .pcswtrg().set_bit()
+                    });
+                }
+
+                /// Enables and disables the end-of-conversion interrupt of the preempted sequence
+                pub fn set_preempted_end_of_conversion_interrupt(&mut self, eoc: bool) {
+                    self.adc_reg.ctrl1().modify(|_, w| w.pcceien().bit(eoc));
+                }
+
+                /// Returns `true` once the preempted sequence has been converted
+                pub fn is_preempted_end_of_conversion(&self) -> bool {
+                    self.adc_reg.sts().read().pcce().bit_is_set()
+                }
+
+                /// Resets the end-of-conversion flag of the preempted sequence
+                pub fn clear_preempted_end_of_conversion_flag(&mut self) {
+                    self.adc_reg.sts().modify(|_, w| w.pcce().clear_bit().pccs().clear_bit());
+                }
+
+                /// Returns the result of the preempted conversion at `sequence`, minus its offset
+                pub fn preempted_sample(&self, sequence: config::PreemptedSequence) -> i16 {
+                    // Right aligned results are sign extended
+                    (match sequence {
+                        config::PreemptedSequence::One   => self.adc_reg.pdt1().read().bits(),
+                        config::PreemptedSequence::Two   => self.adc_reg.pdt2().read().bits(),
+                        config::PreemptedSequence::Three => self.adc_reg.pdt3().read().bits(),
+                        config::PreemptedSequence::Four  => self.adc_reg.pdt4().read().bits(),
+                    }) as u16 as i16
+                }
+
+                /// Returns the results of all four preempted conversions
+                pub fn preempted_samples(&self) -> [i16; 4] {
+                    [
+                        config::PreemptedSequence::One,
+                        config::PreemptedSequence::Two,
+                        config::PreemptedSequence::Three,
+                        config::PreemptedSequence::Four,
+                    ]
+                    .map(|sequence| self.preempted_sample(sequence))
+                }
+
+                /// Number of conversions in the regular sequence
+                pub fn sequence_len(&self) -> usize {
+                    self.adc_reg.osq1().read().oclen().bits() as usize + 1
+                }
+
+                /// Converts the regular sequence into `buf` by DMA
+                ///
+                /// The sequence is set up with `configure_channel` before. `mode` selects if `buf` is
+                /// filled once or circularly, and the length of `buf` must be a multiple of the
+                /// sequence length, for circular transfers of twice the sequence length.
+                /// On devices with fixed DMA mapping `dma_channel` must be the channel of the ADC.
+                pub fn scan<DMA, const N: u8>(
+                    mut self,
+                    dma_channel: dma::Channel<DMA, N>,
+                    buf: &'static mut [u16],
+                    mode: config::ScanMode,
+                ) -> Scan<pac::$adc_type, DMA, N>
+                where
+                    DMA: dma::traits::Instance,
+                {
+                    let circular = mode == config::ScanMode::Circular;
+                    let sequence_len = self.sequence_len() * if circular { 2 } else { 1 };
+                    assert!(!buf.is_empty() && buf.len() % sequence_len == 0);
+
+                    let config = self.config;
+                    self.set_sequence(config::SequenceMode::Enabled);
+                    self.set_dma(mode.into());
+
+                    let len = buf.len();
+                    Scan {
+                        transfer: Transfer::init_peripheral_to_memory(dma_channel, self, buf, circular),
+                        len,
+                        config,
+                    }
+                }
+
                 /// Returns the current sample stored in the ADC data register
                 pub fn current_sample(&self) -> u16 {
                     self.adc_reg.odt().read().odt().bits()
@@ -888,6 +1371,64 @@ macro_rules! adc {
                 }
             }
 
+            unsafe impl PeriAddress for Adc<pac::$adc_type> {
+                type MemSize = u16;
+
+                fn address(&self) -> u32 {
+                    self.adc_reg.odt().as_ptr() as u32
+                }
+            }
+
+            unsafe impl DMASet<PeripheralToMemory> for Adc<pac::$adc_type> {}
+
+            impl<DMA: dma::traits::Instance, const N: u8> Scan<pac::$adc_type, DMA, N> {
+                /// Start the DMA and the first sequence
+                pub fn start(&mut self) {
+                    self.transfer.start();
+                    let adc = self.transfer.peripheral_mut();
+                    adc.set_continuous(adc.config.continuous);
+                    adc.start_conversion();
+                }
+
+                /// Start the next sequence by software
+                pub fn trigger(&mut self) {
+                    self.transfer.peripheral_mut().start_conversion();
+                }
+
+                /// Stop converting after the current sequence and pause the DMA
+                pub fn stop(&mut self) {
+                    let adc = self.transfer.peripheral_mut();
+                    adc.adc_reg.ctrl2().modify(|_, w| w.rpen().disable());
+                    self.transfer.pause();
+                }
+
+                /// Block until a single transfer has filled the buffer and release its parts
+                pub fn wait(self) -> (Adc<pac::$adc_type>, dma::Channel<DMA, N>, &'static mut [u16]) {
+                    let config = self.config;
+                    let (channel, adc, buf) = self.transfer.wait();
+                    Self::restore(adc, channel, buf, config)
+                }
+
+                /// Stop the conversions and the DMA and release their parts
+                pub fn release(self) -> (Adc<pac::$adc_type>, dma::Channel<DMA, N>, &'static mut [u16]) {
+                    let config = self.config;
+                    let (channel, adc, buf) = self.transfer.release();
+                    Self::restore(adc, channel, buf, config)
+                }
+
+                fn restore(
+                    mut adc: Adc<pac::$adc_type>,
+                    channel: dma::Channel<DMA, N>,
+                    buf: &'static mut [u16],
+                    config: config::AdcConfig,
+                ) -> (Adc<pac::$adc_type>, dma::Channel<DMA, N>, &'static mut [u16]) {
+                    adc.adc_reg.ctrl2().modify(|_, w| w.rpen().disable());
+                    // Back to the configuration from before the scan
+                    adc.apply_config(config);
+                    (adc, channel, buf)
+                }
+            }
+
         )+
     };
 }
@@ -81,5 +81,14 @@ macro_rules! bus {
     }
 }
 
-#[cfg(any(feature = "at32f415", feature = "at32f421"))]
+#[cfg(any(feature = "legacy-gpio", feature = "at32f421", feature = "at32f425"))]
 mod f1;
+
+#[cfg(any(
+    feature = "at32f402",
+    feature = "at32f405",
+    feature = "at32f423",
+    feature = "at32f435",
+    feature = "at32f437"
+))]
+mod f4;
@@ -35,6 +35,11 @@ bus! {
     IOMUX => (APB2, 0),
 }
 
+#[cfg(any(feature = "at32f421", feature = "at32f425"))]
+bus! {
+    SCFG => (APB2, 0),
+}
+
 #[cfg(not(any(feature = "at32f421", feature = "at32f425")))]
 #[cfg(feature = "gpiod")]
 bus! {
@@ -58,11 +63,22 @@ bus! {
     SPI2 => (APB1, 14),
 }
 
+#[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+bus! {
+    SPI3 => (APB1, 15),
+    SPI4 => (APB1, 16),
+}
+
 bus! {
     I2C1 => (APB1, 21),
     I2C2 => (APB1, 22),
 }
 
+#[cfg(feature = "i2c3")]
+bus! {
+    I2C3 => (APB2, 23),
+}
+
 bus! {
     USART1 => (APB2, 14),
     USART2 => (APB1, 17),
@@ -79,6 +95,36 @@ bus! {
     UART5 => (APB1, 20),
 }
 
+#[cfg(feature = "usart6")]
+bus! {
+    USART6 => (APB2, 24),
+}
+
+#[cfg(feature = "uart7")]
+bus! {
+    UART7 => (APB2, 25),
+}
+
+#[cfg(feature = "uart8")]
+bus! {
+    UART8 => (APB2, 26),
+}
+
+#[cfg(feature = "usart4")]
+bus! {
+    USART4 => (APB1, 19),
+}
+
+#[cfg(feature = "can1")]
+bus! {
+    CAN1 => (APB1, 25),
+}
+
+#[cfg(feature = "can2")]
+bus! {
+    CAN2 => (APB1, 26),
+}
+
 bus! {
     ADC1 => (APB2, 9),
 }
@@ -130,7 +176,7 @@ bus! {
 
 #[cfg(feature = "tmr10")]
 bus! {
-    TMR10 => (APB2, 10),
+    TMR10 => (APB2, 20),
 }
 
 #[cfg(feature = "tmr11")]
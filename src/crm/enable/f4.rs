@@ -1,6 +1,202 @@
 use super::*;
 
 bus! {
-    DMA1 => (APB1, 0),
-    CRC => (APB1, 6),
-}
\ No newline at end of file
+    CRC => (AHB1, 12),
+    DMA1 => (AHB1, 22),
+    DMA2 => (AHB1, 24),
+}
+
+bus! {
+    GPIOA => (AHB1, 0),
+    GPIOB => (AHB1, 1),
+    GPIOC => (AHB1, 2),
+}
+
+#[cfg(feature = "gpiod")]
+bus! {
+    GPIOD => (AHB1, 3),
+}
+
+#[cfg(feature = "gpioe")]
+bus! {
+    GPIOE => (AHB1, 4),
+}
+
+#[cfg(feature = "gpiof")]
+bus! {
+    GPIOF => (AHB1, 5),
+}
+
+#[cfg(feature = "gpiog")]
+bus! {
+    GPIOG => (AHB1, 6),
+}
+
+#[cfg(feature = "gpioh")]
+bus! {
+    GPIOH => (AHB1, 7),
+}
+
+#[cfg(feature = "emac")]
+bus! {
+    EMAC => (AHB1, 25),
+}
+
+bus! {
+    SCFG => (APB2, 14),
+}
+
+bus! {
+    SPI1 => (APB2, 12),
+    SPI2 => (APB1, 14),
+    SPI3 => (APB1, 15),
+}
+
+#[cfg(any(feature = "at32f435", feature = "at32f437"))]
+bus! {
+    SPI4 => (APB2, 13),
+}
+
+bus! {
+    I2C1 => (APB1, 21),
+    I2C2 => (APB1, 22),
+}
+
+#[cfg(feature = "i2c3")]
+bus! {
+    I2C3 => (APB1, 23),
+}
+
+#[cfg(feature = "can1")]
+bus! {
+    CAN1 => (APB1, 25),
+}
+
+#[cfg(feature = "can2")]
+bus! {
+    CAN2 => (APB1, 26),
+}
+
+bus! {
+    USART1 => (APB2, 4),
+    USART2 => (APB1, 17),
+}
+
+#[cfg(feature = "usart3")]
+bus! {
+    USART3 => (APB1, 18),
+}
+
+#[cfg(feature = "usart4")]
+bus! {
+    USART4 => (APB1, 19),
+}
+
+#[cfg(feature = "usart5")]
+bus! {
+    USART5 => (APB1, 20),
+}
+
+#[cfg(feature = "usart6")]
+bus! {
+    USART6 => (APB2, 5),
+}
+
+#[cfg(feature = "uart4")]
+bus! {
+    UART4 => (APB1, 19),
+}
+
+#[cfg(feature = "uart5")]
+bus! {
+    UART5 => (APB1, 20),
+}
+
+#[cfg(feature = "uart7")]
+bus! {
+    UART7 => (APB1, 30),
+}
+
+#[cfg(feature = "uart8")]
+bus! {
+    UART8 => (APB1, 31),
+}
+
+bus! {
+    ADC1 => (APB2, 8),
+}
+
+#[cfg(feature = "tmr1")]
+bus! {
+    TMR1 => (APB2, 0),
+}
+
+#[cfg(feature = "tmr2")]
+bus! {
+    TMR2 => (APB1, 0),
+}
+
+#[cfg(feature = "tmr3")]
+bus! {
+    TMR3 => (APB1, 1),
+}
+
+#[cfg(feature = "tmr4")]
+bus! {
+    TMR4 => (APB1, 2),
+}
+
+#[cfg(feature = "tmr5")]
+bus! {
+    TMR5 => (APB1, 3),
+}
+
+#[cfg(feature = "tmr6")]
+bus! {
+    TMR6 => (APB1, 4),
+}
+
+#[cfg(feature = "tmr7")]
+bus! {
+    TMR7 => (APB1, 5),
+}
+
+#[cfg(feature = "tmr8")]
+bus! {
+    TMR8 => (APB2, 1),
+}
+
+#[cfg(feature = "tmr9")]
+bus! {
+    TMR9 => (APB2, 16),
+}
+
+#[cfg(feature = "tmr10")]
+bus! {
+    TMR10 => (APB2, 17),
+}
+
+#[cfg(feature = "tmr11")]
+bus! {
+    TMR11 => (APB2, 18),
+}
+
+#[cfg(feature = "tmr12")]
+bus! {
+    TMR12 => (APB1, 6),
+}
+
+#[cfg(feature = "tmr13")]
+bus! {
+    TMR13 => (APB1, 7),
+}
+
+#[cfg(feature = "tmr14")]
+bus! {
+    TMR14 => (APB1, 8),
+}
+
+#[cfg(feature = "tmr20")]
+bus! {
+    TMR20 => (APB2, 20),
+}
@@ -0,0 +1,458 @@
+//! Direct Memory Access
+//!
+//! AT32 DMA controllers have up to 7 channels. On devices with the DMAMUX block
+//! (F402/F405/F423/F435/F437) any request can be routed to any channel with
+//! [`Channel::set_request`], on other devices every peripheral request is wired to
+//! a fixed channel listed in the reference manual, and that channel must be used.
+//!
+//! ```rust
+//! let dma1 = dp.DMA1.split();
+//! let transfer = Transfer::init_memory_to_peripheral(dma1.2, peripheral, &BUFFER, false);
+//! transfer.start();
+//! ```
+
+use core::marker::PhantomData;
+use core::ptr;
+use core::sync::atomic::{compiler_fence, Ordering};
+
+use crate::crm::{Enable, Reset};
+use crate::pac;
+
+pub mod traits {
+    use super::*;
+
+    /// Peripheral register that can be used as source or destination of a DMA transfer
+    ///
+    /// # Safety
+    ///
+    /// `address` must return the address of a register the DMA may access with `MemSize` words.
+    pub unsafe trait PeriAddress {
+        type MemSize: Word;
+
+        /// Access width of the register, defaults to the width of `MemSize`
+        #[doc(hidden)]
+        const PERIPHERAL_WIDTH: u32 = <Self::MemSize as Word>::WIDTH;
+
+        fn address(&self) -> u32;
+    }
+
+    /// Marks peripherals that issue DMA requests for transfers in direction `DIR`
+    ///
+    /// # Safety
+    ///
+    /// The peripheral must generate the request needed to pace transfers in `DIR`.
+    pub unsafe trait DMASet<DIR> {}
+
+    /// Transfer direction
+    pub trait Direction: crate::Sealed {
+        #[doc(hidden)]
+        const MEMORY_TO_PERIPHERAL: bool;
+    }
+
+    /// DMA data unit
+    pub trait Word: crate::Sealed + Copy {
+        #[doc(hidden)]
+        const WIDTH: u32;
+    }
+
+    /// DMA controller
+    pub trait Instance: crate::Sealed + Enable + Reset {
+        #[doc(hidden)]
+        fn base() -> usize;
+    }
+}
+use traits::{DMASet, Direction, Instance, PeriAddress, Word};
+
+/// Transfer from memory to a peripheral register
+pub struct MemoryToPeripheral;
+/// Transfer from a peripheral register to memory
+pub struct PeripheralToMemory;
+
+impl crate::Sealed for MemoryToPeripheral {}
+impl Direction for MemoryToPeripheral {
+    const MEMORY_TO_PERIPHERAL: bool = true;
+}
+
+impl crate::Sealed for PeripheralToMemory {}
+impl Direction for PeripheralToMemory {
+    const MEMORY_TO_PERIPHERAL: bool = false;
+}
+
+macro_rules! word {
+    ($($T:ty => $width:literal,)+) => {
+        $(
+            impl crate::Sealed for $T {}
+            impl Word for $T {
+                const WIDTH: u32 = $width;
+            }
+        )+
+    };
+}
+
+word! {
+    u8 => 0b00,
+    u16 => 0b01,
+    u32 => 0b10,
+}
+
+impl crate::Sealed for pac::DMA1 {}
+impl Instance for pac::DMA1 {
+    #[inline(always)]
+    fn base() -> usize {
+        pac::DMA1::ptr() as usize
+    }
+}
+
+#[cfg(feature = "dma2")]
+impl crate::Sealed for pac::DMA2 {}
+#[cfg(feature = "dma2")]
+impl Instance for pac::DMA2 {
+    #[inline(always)]
+    fn base() -> usize {
+        pac::DMA2::ptr() as usize
+    }
+}
+
+bitflags::bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct Event: u32 {
+        const TransferComplete = 1 << 1;
+        const HalfTransfer = 1 << 2;
+        const TransferError = 1 << 3;
+    }
+}
+
+/// Channel priority level
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum Priority {
+    Low = 0,
+    Medium = 1,
+    High = 2,
+    VeryHigh = 3,
+}
+
+/// Extension trait to split a DMA peripheral into independent channels
+pub trait DmaExt: Instance + Sized {
+    fn split(self) -> Channels<Self>;
+}
+
+impl<DMA: Instance> DmaExt for DMA {
+    fn split(self) -> Channels<Self> {
+        unsafe {
+            DMA::enable_unchecked();
+            DMA::reset_unchecked();
+        }
+        Channels(
+            Channel::new(),
+            Channel::new(),
+            Channel::new(),
+            Channel::new(),
+            Channel::new(),
+            Channel::new(),
+            Channel::new(),
+        )
+    }
+}
+
+/// All channels of a DMA controller
+///
+/// Channels that are not implemented on a particular controller must not be used.
+pub struct Channels<DMA>(
+    pub Channel<DMA, 1>,
+    pub Channel<DMA, 2>,
+    pub Channel<DMA, 3>,
+    pub Channel<DMA, 4>,
+    pub Channel<DMA, 5>,
+    pub Channel<DMA, 6>,
+    pub Channel<DMA, 7>,
+);
+
+// Channel registers: CTRL, DTCNT, PADDR, MADDR repeated with a 0x14 stride
+const STS: usize = 0x00;
+const CLR: usize = 0x04;
+const CTRL: usize = 0x08;
+const DTCNT: usize = 0x0c;
+const PADDR: usize = 0x10;
+const MADDR: usize = 0x14;
+const CH_STRIDE: usize = 0x14;
+#[cfg(feature = "dmamux")]
+const MUXSEL: usize = 0x100;
+#[cfg(feature = "dmamux")]
+const MUXCCTRL: usize = 0x104;
+
+/// DMA channel `N` of controller `DMA`
+pub struct Channel<DMA, const N: u8> {
+    _dma: PhantomData<DMA>,
+}
+
+impl<DMA: Instance, const N: u8> Channel<DMA, N> {
+    fn new() -> Self {
+        Self { _dma: PhantomData }
+    }
+
+    #[inline(always)]
+    fn reg(offset: usize) -> *mut u32 {
+        (DMA::base() + offset + CH_STRIDE * (N as usize - 1)) as *mut u32
+    }
+
+    #[inline(always)]
+    fn modify_ctrl(f: impl FnOnce(u32) -> u32) {
+        unsafe {
+            let r = Self::reg(CTRL);
+            ptr::write_volatile(r, f(ptr::read_volatile(r)));
+        }
+    }
+
+    /// Route DMAMUX request `request` to this channel
+    ///
+    /// Request numbers are listed in the DMAMUX section of the reference manual.
+    #[cfg(feature = "dmamux")]
+    pub fn set_request(&mut self, request: u8) {
+        unsafe {
+            let muxsel = (DMA::base() + MUXSEL) as *mut u32;
+            ptr::write_volatile(muxsel, ptr::read_volatile(muxsel) | 1);
+            let ctrl = (DMA::base() + MUXCCTRL + 4 * (N as usize - 1)) as *mut u32;
+            ptr::write_volatile(ctrl, (ptr::read_volatile(ctrl) & !0x7f) | request as u32);
+        }
+    }
+
+    /// Set the priority used when several channels are requested at the same time
+    pub fn set_priority(&mut self, priority: Priority) {
+        Self::modify_ctrl(|r| (r & !(0b11 << 12)) | ((priority as u32) << 12));
+    }
+
+    /// Enable interrupts for `event`
+    pub fn listen(&mut self, event: Event) {
+        Self::modify_ctrl(|r| r | event.bits());
+    }
+
+    /// Disable interrupts for `event`
+    pub fn unlisten(&mut self, event: Event) {
+        Self::modify_ctrl(|r| r & !event.bits());
+    }
+
+    /// Get the events flagged for this channel
+    pub fn events(&self) -> Event {
+        let sts = unsafe { ptr::read_volatile((DMA::base() + STS) as *const u32) };
+        Event::from_bits_truncate(sts >> (4 * (N as u32 - 1)))
+    }
+
+    /// Clear the flags of `event`
+    pub fn clear_events(&mut self, event: Event) {
+        // The global flag is cleared together with the others
+        let bits = (event.bits() | 1) << (4 * (N as u32 - 1));
+        unsafe { ptr::write_volatile((DMA::base() + CLR) as *mut u32, bits) }
+    }
+
+    /// Number of data items left to transfer
+    pub fn remaining(&self) -> u16 {
+        unsafe { ptr::read_volatile(Self::reg(DTCNT)) as u16 }
+    }
+
+    /// Returns `true` while the channel is enabled
+    pub fn is_enabled(&self) -> bool {
+        unsafe { ptr::read_volatile(Self::reg(CTRL)) & 1 != 0 }
+    }
+
+    /// Enable the channel and start the transfer
+    pub fn enable(&mut self) {
+        compiler_fence(Ordering::Release);
+        Self::modify_ctrl(|r| r | 1);
+    }
+
+    /// Disable the channel
+    pub fn disable(&mut self) {
+        Self::modify_ctrl(|r| r & !1);
+        compiler_fence(Ordering::Acquire);
+    }
+
+    /// Program a transfer of `len` words between `peripheral` and `memory` addresses
+    ///
+    /// The DMA accesses the peripheral with `PERI::PERIPHERAL_WIDTH` and memory with `PERI::MemSize` words.
+    ///
+    /// # Safety
+    ///
+    /// Both addresses must stay valid for the whole transfer.
+    pub unsafe fn configure<PERI: PeriAddress, DIR: Direction>(
+        &mut self,
+        peripheral: u32,
+        memory: u32,
+        len: u16,
+        circular: bool,
+    ) {
+        self.disable();
+        self.clear_events(Event::all());
+        ptr::write_volatile(Self::reg(PADDR), peripheral);
+        ptr::write_volatile(Self::reg(MADDR), memory);
+        ptr::write_volatile(Self::reg(DTCNT), len as u32);
+        Self::modify_ctrl(|r| {
+            // Keep the priority and the interrupt enables, MINCM is always set
+            (r & (0b11 << 12 | Event::all().bits()))
+                | ((DIR::MEMORY_TO_PERIPHERAL as u32) << 4)
+                | ((circular as u32) << 5)
+                | (1 << 7)
+                | (PERI::PERIPHERAL_WIDTH << 8)
+                | (<PERI::MemSize as Word>::WIDTH << 10)
+        });
+    }
+}
+
+/// DMA transfer between a peripheral register and a static buffer
+pub struct Transfer<CH, PERI, BUF, DIR> {
+    channel: CH,
+    peripheral: PERI,
+    buf: BUF,
+    _dir: PhantomData<DIR>,
+}
+
+impl<DMA, const N: u8, PERI, W> Transfer<Channel<DMA, N>, PERI, &'static [W], MemoryToPeripheral>
+where
+    DMA: Instance,
+    PERI: PeriAddress<MemSize = W> + DMASet<MemoryToPeripheral>,
+    W: Word,
+{
+    /// Prepare a transfer of `buf` to `peripheral`, restarted from the beginning if `circular`
+    pub fn init_memory_to_peripheral(
+        mut channel: Channel<DMA, N>,
+        peripheral: PERI,
+        buf: &'static [W],
+        circular: bool,
+    ) -> Self {
+        assert!(buf.len() <= u16::MAX as usize);
+        unsafe {
+            channel.configure::<PERI, MemoryToPeripheral>(
+                peripheral.address(),
+                buf.as_ptr() as u32,
+                buf.len() as u16,
+                circular,
+            );
+        }
+        Self {
+            channel,
+            peripheral,
+            buf,
+            _dir: PhantomData,
+        }
+    }
+}
+
+impl<DMA, const N: u8, PERI, W>
+    Transfer<Channel<DMA, N>, PERI, &'static mut [W], PeripheralToMemory>
+where
+    DMA: Instance,
+    PERI: PeriAddress<MemSize = W> + DMASet<PeripheralToMemory>,
+    W: Word,
+{
+    /// Prepare a transfer from `peripheral` into `buf`, restarted from the beginning if `circular`
+    pub fn init_peripheral_to_memory(
+        mut channel: Channel<DMA, N>,
+        peripheral: PERI,
+        buf: &'static mut [W],
+        circular: bool,
+    ) -> Self {
+        assert!(buf.len() <= u16::MAX as usize);
+        unsafe {
+            channel.configure::<PERI, PeripheralToMemory>(
+                peripheral.address(),
+                buf.as_mut_ptr() as u32,
+                buf.len() as u16,
+                circular,
+            );
+        }
+        Self {
+            channel,
+            peripheral,
+            buf,
+            _dir: PhantomData,
+        }
+    }
+
+    /// Access the buffer while the transfer is stopped
+    pub fn buffer(&mut self) -> Option<&mut [W]> {
+        if self.channel.is_enabled() {
+            None
+        } else {
+            compiler_fence(Ordering::Acquire);
+            Some(&mut *self.buf)
+        }
+    }
+
+    /// Raw pointer to the buffer, for reading the part the DMA is done with while the
+    /// transfer is running
+    ///
+    /// No reference to the buffer is created, so the DMA may keep writing other parts.
+    pub fn buffer_ptr(&self) -> *const [W] {
+        core::ptr::addr_of!(*self.buf)
+    }
+}
+
+impl<DMA: Instance, const N: u8, PERI, BUF, DIR> Transfer<Channel<DMA, N>, PERI, BUF, DIR> {
+    /// Start the transfer
+    pub fn start(&mut self) {
+        self.channel.enable();
+    }
+
+    /// Pause the transfer, it can be continued with [`start`](Self::start)
+    pub fn pause(&mut self) {
+        self.channel.disable();
+    }
+
+    /// Returns `true` once all data has been transferred
+    pub fn is_complete(&self) -> bool {
+        self.channel.events().contains(Event::TransferComplete)
+    }
+
+    /// Returns `true` once half of the data has been transferred
+    pub fn is_half_complete(&self) -> bool {
+        self.channel.events().contains(Event::HalfTransfer)
+    }
+
+    /// Returns `true` if a bus error stopped the transfer
+    pub fn has_error(&self) -> bool {
+        self.channel.events().contains(Event::TransferError)
+    }
+
+    /// Clear the flags of `event`, usually from the DMA channel interrupt
+    pub fn clear_events(&mut self, event: Event) {
+        self.channel.clear_events(event);
+    }
+
+    /// Enable interrupts for `event`
+    pub fn listen(&mut self, event: Event) {
+        self.channel.listen(event);
+    }
+
+    /// Disable interrupts for `event`
+    pub fn unlisten(&mut self, event: Event) {
+        self.channel.unlisten(event);
+    }
+
+    /// Number of data items left until the end of the buffer
+    pub fn remaining(&self) -> u16 {
+        self.channel.remaining()
+    }
+
+    pub fn peripheral(&self) -> &PERI {
+        &self.peripheral
+    }
+
+    pub fn peripheral_mut(&mut self) -> &mut PERI {
+        &mut self.peripheral
+    }
+
+    /// Block until a single transfer is complete and release its parts
+    pub fn wait(mut self) -> (Channel<DMA, N>, PERI, BUF) {
+        while !self.is_complete() && !self.has_error() {}
+        self.channel.disable();
+        (self.channel, self.peripheral, self.buf)
+    }
+
+    /// Stop the transfer and release its parts
+    pub fn release(mut self) -> (Channel<DMA, N>, PERI, BUF) {
+        self.channel.disable();
+        self.channel.clear_events(Event::all());
+        (self.channel, self.peripheral, self.buf)
+    }
+}
@@ -65,7 +65,11 @@ pub use partially_erased::{PEPin, PartiallyErasedPin};
 mod erased;
 pub use erased::{EPin, ErasedPin};
 mod dynamic;
-pub use dynamic::{Dynamic, DynamicPin};
+pub use dynamic::{Dynamic, DynamicPin, PinModeError};
+mod exti;
+#[cfg(feature = "async")]
+pub use exti::on_interrupt as on_exint_interrupt;
+pub use exti::{ExintMux, ExtiPin};
 
 pub use embedded_hal::digital::*;
 
@@ -178,6 +182,9 @@ impl<Otype> marker::NotAlt for Output<Otype> {}
 impl marker::NotAlt for Analog {}
 
 /// GPIO Pin speed selection
+///
+/// On legacy-gpio parts the speed selects the output mode in `cfglr`/`cfghr`,
+/// on new-gpio parts it is mapped onto [`DriveStrength`].
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum Speed {
@@ -189,6 +196,27 @@ pub enum Speed {
     High = 3,
 }
 
+/// Output drive strength selection (`odrvr` register)
+#[cfg(feature = "new-gpio")]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DriveStrength {
+    /// Stronger sourcing/sinking strength
+    Stronger = 1,
+    /// Moderate sourcing/sinking strength
+    Moderate = 2,
+}
+
+#[cfg(feature = "new-gpio")]
+impl From<Speed> for DriveStrength {
+    fn from(speed: Speed) -> Self {
+        match speed {
+            Speed::Low | Speed::Medium => Self::Moderate,
+            Speed::High => Self::Stronger,
+        }
+    }
+}
+
 /// GPIO interrupt trigger edge selection
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -278,6 +306,30 @@ pub trait PinPull: Sized {
     }
 }
 
+/// Drive strength and huge sink current configuration
+#[cfg(feature = "new-gpio")]
+pub trait PinDrive: Sized {
+    /// Set output drive strength
+    fn set_drive_strength(&mut self, drive: DriveStrength);
+
+    #[inline(always)]
+    fn drive_strength(mut self, drive: DriveStrength) -> Self {
+        self.set_drive_strength(drive);
+        self
+    }
+
+    /// Enables / disables huge sink current mode
+    ///
+    /// Only pins marked as high current (HDRV) in the datasheet support this mode
+    fn set_huge_sink(&mut self, on: bool);
+
+    #[inline(always)]
+    fn huge_sink(mut self, on: bool) -> Self {
+        self.set_huge_sink(on);
+        self
+    }
+}
+
 impl<const P: char, const N: u8, MODE> PinSpeed for Pin<P, N, MODE>
 where
     MODE: marker::OutputSpeed,
@@ -288,6 +340,22 @@ where
     }
 }
 
+#[cfg(feature = "new-gpio")]
+impl<const P: char, const N: u8, MODE> PinDrive for Pin<P, N, MODE>
+where
+    MODE: marker::OutputSpeed,
+{
+    #[inline(always)]
+    fn set_drive_strength(&mut self, drive: DriveStrength) {
+        self.set_drive_strength(drive)
+    }
+
+    #[inline(always)]
+    fn set_huge_sink(&mut self, on: bool) {
+        self.set_huge_sink(on)
+    }
+}
+
 impl<const P: char, const N: u8, MODE> PinPull for Pin<P, N, MODE>
 where
     MODE: marker::Active,
@@ -582,6 +650,10 @@ impl<const P: char> Gpio<P> {
             'D' => crate::pac::GPIOD::ptr() as _,
             #[cfg(feature = "gpiof")]
             'F' => crate::pac::GPIOF::ptr() as _,
+            #[cfg(feature = "gpiog")]
+            'G' => crate::pac::GPIOG::ptr() as _,
+            #[cfg(feature = "gpioh")]
+            'H' => crate::pac::GPIOH::ptr() as _,
             _ => panic!("Unknown GPIO port"),
         }
     }
@@ -1,6 +1,6 @@
-#[cfg(feature = "at32f415")]
+#[cfg(feature = "legacy-gpio")]
 mod f1;
-#[cfg(feature = "at32f415")]
+#[cfg(feature = "legacy-gpio")]
 pub use f1::*;
 
 #[cfg(feature = "at32f421")]
@@ -8,6 +8,30 @@ mod f4;
 #[cfg(feature = "at32f421")]
 pub use f4::*;
 
+// The tables have no ADC pins: the external ADC triggers (EXINT11 for the ordinary and
+// EXINT15 for the preempted sequence) reach the ADC from any port through the EXINT mux,
+// see `gpio::exti`, not through an alternate function.
+
+#[cfg(any(feature = "at32f402", feature = "at32f405"))]
+mod f402;
+#[cfg(any(feature = "at32f402", feature = "at32f405"))]
+pub use f402::*;
+
+#[cfg(feature = "at32f423")]
+mod f423;
+#[cfg(feature = "at32f423")]
+pub use f423::*;
+
+#[cfg(feature = "at32f425")]
+mod f425;
+#[cfg(feature = "at32f425")]
+pub use f425::*;
+
+#[cfg(any(feature = "at32f435", feature = "at32f437"))]
+mod f435;
+#[cfg(any(feature = "at32f435", feature = "at32f437"))]
+pub use f435::*;
+
 // macro_rules! extipin {
 //     ($( $(#[$attr:meta])* $PX:ident,)*) => {
 //         fn make_interrupt_source(&mut self, _syscfg: &mut $crate::syscfg::SysCfg) {
@@ -71,6 +95,40 @@ pub use f4::*;
 // }
 // use extipin;
 
+/// Returns `true` if a pin appears more than once in the pin list of a signal
+///
+/// Used by `pin!` to reject duplicated table entries and pins listed with two
+/// alternate functions for the same signal at compile time.
+pub(crate) const fn has_duplicate_pins(pins: &[&str]) -> bool {
+    const fn str_eq(a: &str, b: &str) -> bool {
+        let (a, b) = (a.as_bytes(), b.as_bytes());
+        if a.len() != b.len() {
+            return false;
+        }
+        let mut i = 0;
+        while i < a.len() {
+            if a[i] != b[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    let mut i = 0;
+    while i < pins.len() {
+        let mut j = i + 1;
+        while j < pins.len() {
+            if str_eq(pins[i], pins[j]) {
+                return true;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    false
+}
+
 macro_rules! pin {
     ( $($(#[$docs:meta])* <$name:ident, $Otype:ident> for $(no: $NoPin:ident,)? [$(
         $(#[$attr:meta])* $PX:ident<$A:literal $(, Speed::$Speed:ident)?>,
@@ -88,6 +146,11 @@ macro_rules! pin {
                 )*
             }
 
+            const _: () = assert!(
+                !$crate::gpio::alt::has_duplicate_pins(&[$($(#[$attr])* stringify!($PX),)*]),
+                concat!("pin listed twice for ", stringify!($name)),
+            );
+
             impl crate::Sealed for $name { }
 
             #[allow(unreachable_patterns)]
@@ -116,6 +179,30 @@ macro_rules! pin {
                 }
             }
 
+            #[cfg(feature = "new-gpio")]
+            #[allow(unreachable_patterns)]
+            impl $crate::gpio::PinDrive for $name {
+                fn set_drive_strength(&mut self, _drive: $crate::gpio::DriveStrength) {
+                    match self {
+                        $(
+                            $(#[$attr])*
+                            Self::$PX(p) => p.set_drive_strength(_drive),
+                        )*
+                        _ => {}
+                    }
+                }
+
+                fn set_huge_sink(&mut self, _on: bool) {
+                    match self {
+                        $(
+                            $(#[$attr])*
+                            Self::$PX(p) => p.set_huge_sink(_on),
+                        )*
+                        _ => {}
+                    }
+                }
+            }
+
             #[allow(unreachable_patterns)]
             impl $crate::gpio::PinPull for $name {
                 fn set_internal_resistor(&mut self, _pull: $crate::gpio::Pull) {
@@ -197,6 +284,11 @@ macro_rules! pin {
                 )*
             }
 
+            const _: () = assert!(
+                !$crate::gpio::alt::has_duplicate_pins(&[$($(#[$attr])* stringify!($PX),)*]),
+                concat!("pin listed twice for ", stringify!($name)),
+            );
+
             impl<Otype> crate::Sealed for $name<Otype> { }
 
             #[allow(unreachable_patterns)]
@@ -225,6 +317,30 @@ macro_rules! pin {
                 }
             }
 
+            #[cfg(feature = "new-gpio")]
+            #[allow(unreachable_patterns)]
+            impl<Otype> $crate::gpio::PinDrive for $name<Otype> {
+                fn set_drive_strength(&mut self, _drive: $crate::gpio::DriveStrength) {
+                    match self {
+                        $(
+                            $(#[$attr])*
+                            Self::$PX(p) => p.set_drive_strength(_drive),
+                        )*
+                        _ => {}
+                    }
+                }
+
+                fn set_huge_sink(&mut self, _on: bool) {
+                    match self {
+                        $(
+                            $(#[$attr])*
+                            Self::$PX(p) => p.set_huge_sink(_on),
+                        )*
+                        _ => {}
+                    }
+                }
+            }
+
             #[allow(unreachable_patterns)]
             impl<Otype> $crate::gpio::PinPull for $name<Otype> {
                 fn set_internal_resistor(&mut self, _pull: $crate::gpio::Pull) {
@@ -293,6 +409,73 @@ macro_rules! pin {
 }
 use pin;
 
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::has_duplicate_pins;
+    use std::collections::BTreeMap;
+    use std::format;
+    use std::string::String;
+    use std::vec::Vec;
+
+    /// Pin/AF pairs of a table claimed by more than one peripheral
+    ///
+    /// An AF number selects a single function of a pin, so a pair may only be shared
+    /// by the signals of one peripheral, e.g. TMR2 CH1 and EXT. `cfg` attributes are
+    /// ignored, every entry of the table is checked.
+    fn af_conflicts(table: &str) -> Vec<String> {
+        let mut owners = BTreeMap::new();
+        let mut conflicts = Vec::new();
+        let mut module = "";
+        for line in table.lines().map(str::trim) {
+            if let Some(name) = line.strip_prefix("pub mod ") {
+                module = name.trim_end_matches(" {");
+            } else if let Some((pin, af)) = line.strip_suffix(">,").and_then(|e| e.split_once('<'))
+            {
+                let af = af.split(',').next().unwrap();
+                if !pin.starts_with('P') || !af.bytes().all(|b| b.is_ascii_digit()) {
+                    continue;
+                }
+                if let Some(other) = owners.insert((pin, af), module) {
+                    if other != module {
+                        conflicts.push(format!("{pin}<{af}>: {other} and {module}"));
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+
+    #[test]
+    fn duplicate_pins() {
+        assert!(!has_duplicate_pins(&[]));
+        assert!(!has_duplicate_pins(&["PA8", "PB13", "PE9"]));
+        assert!(!has_duplicate_pins(&["PA1", "PA10", "PA11"]));
+        assert!(has_duplicate_pins(&["PA8", "PB13", "PA8"]));
+        assert!(has_duplicate_pins(&["PB13", "PB13"]));
+    }
+
+    #[test]
+    fn af_conflicts_detected() {
+        let table = "pub mod a {\n PA9<1>,\n PA9<1>,\n PA10<1, Speed::VeryHigh>,\n}\n\
+                     pub mod b {\n PA9<2>,\n PA10<1>,\n}\n";
+        assert_eq!(af_conflicts(table), ["PA10<1>: a and b"]);
+    }
+
+    #[test]
+    fn no_af_conflicts() {
+        for (family, table) in [
+            ("f402", include_str!("alt/f402.rs")),
+            ("f423", include_str!("alt/f423.rs")),
+            ("f425", include_str!("alt/f425.rs")),
+            ("f435", include_str!("alt/f435.rs")),
+        ] {
+            assert_eq!(af_conflicts(table), Vec::<String>::new(), "{family}");
+        }
+    }
+}
+
 // CAN pins
 #[cfg(feature = "can1")]
 pub trait CanCommon {
@@ -1,5 +1,8 @@
+//! `A` of `PXn<A>` is a mask of the IOMUX remap modes routing the signal to the pin,
+//! see [`crate::iomux`].
+
 use super::*;
-use crate::gpio::{self, NoPin, PushPull};
+use crate::gpio::{self, Input, NoPin, OpenDrain, PushPull};
 
 #[cfg(feature = "tmr1")]
 pub mod tmr1 {
@@ -7,60 +10,79 @@ pub mod tmr1 {
 
     pin! {
         <Ch1> default: PushPull for [
-            PA8<1>,
-
-            #[cfg(feature = "f403a-peripheral")]
-            PE9<1>,
+            PA8<3>,
 
             #[cfg(feature = "f415-peripheral")]
-            PC6<1>,
+            PC6<2>,
+
+            #[cfg(feature = "gpioe")]
+            PE9<8>,
         ],
 
         <Ch1N> default: PushPull for [
-            PA7<1>,
+            PA7<2>,
 
             PB13<1>,
+
+            #[cfg(feature = "gpioe")]
+            PE8<8>,
         ],
 
         <Ch2> default: PushPull for [
-            PA9<1>,
+            PA9<3>,
 
+            #[cfg(feature = "gpioe")]
+            PE11<8>,
         ],
 
         <Ch2N> default: PushPull for [
-            PB0<1>,
+            PB0<2>,
 
             PB14<1>,
 
+            #[cfg(feature = "gpioe")]
+            PE10<8>,
         ],
 
         <Ch3> default: PushPull for [
-            PA10<1>,
+            PA10<3>,
 
+            #[cfg(feature = "gpioe")]
+            PE13<8>,
         ],
 
         <Ch3N> default: PushPull for [
-            PB1<1>,
+            PB1<2>,
 
             PB15<1>,
 
+            #[cfg(feature = "gpioe")]
+            PE12<8>,
         ],
 
         <Ch4> default: PushPull for [
-            PA11<1>,
+            PA11<3>,
 
+            #[cfg(feature = "gpioe")]
+            PE14<8>,
         ],
     }
 
     pin! {
-        <Bkin, PushPull> for [
-            PA6<1>,
+        <Bkin, Input> for [
+            PA6<2>,
 
             PB12<1>,
+
+            #[cfg(feature = "gpioe")]
+            PE15<8>,
         ],
 
-        <Ext, PushPull> for [
-            PA12<1>,
+        <Ext, Input> for [
+            PA12<3>,
+
+            #[cfg(feature = "gpioe")]
+            PE7<8>,
         ],
     }
 
@@ -101,38 +123,35 @@ pub mod tmr2 {
 
     pin! {
         <Ch1> default: PushPull for [
-            PA0<1>,
-
-            PA15<1>,
+            PA0<5>,
 
+            PA15<10>,
         ],
 
         <Ch2> default: PushPull for [
-            PA1<1>,
-
-            PB3<1>,
+            PA1<5>,
 
+            PB3<10>,
         ],
 
         <Ch3> default: PushPull for [
-            PA2<1>,
+            PA2<3>,
 
-            PB10<1>,
+            PB10<12>,
         ],
 
         <Ch4> default: PushPull for [
-            PA3<1>,
+            PA3<3>,
 
-            PB11<1>,
+            PB11<12>,
         ],
     }
 
     pin! {
-        <Ext, PushPull> for [
-            PA0<1>,
-
-            PA15<1>,
+        <Ext, Input> for [
+            PA0<5>,
 
+            PA15<10>,
         ],
     }
 
@@ -163,35 +182,35 @@ pub mod tmr3 {
         <Ch1> default: PushPull for [
             PA6<1>,
 
-            PB4<1>,
+            PB4<4>,
 
-            PC6<1>,
+            PC6<8>,
         ],
 
         <Ch2> default: PushPull for [
             PA7<1>,
 
-            PB5<1>,
+            PB5<4>,
 
-            PC7<1>,
+            PC7<8>,
         ],
 
         <Ch3> default: PushPull for [
-            PB0<1>,
+            PB0<5>,
 
-            PC8<1>,
+            PC8<8>,
         ],
 
         <Ch4> default: PushPull for [
-            PB1<1>,
+            PB1<5>,
 
-            PC9<1>,
+            PC9<8>,
         ],
     }
 
     pin! {
-        <Ext, PushPull> for [
-            PD2<1>,
+        <Ext, Input> for [
+            PD2<13>,
         ],
     }
 
@@ -221,18 +240,37 @@ pub mod tmr4 {
     pin! {
         <Ch1> default: PushPull for [
             PB6<1>,
+
+            #[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+            PD12<2>,
         ],
 
         <Ch2> default: PushPull for [
             PB7<1>,
+
+            #[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+            PD13<2>,
         ],
 
         <Ch3> default: PushPull for [
             PB8<1>,
+
+            #[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+            PD14<2>,
         ],
 
         <Ch4> default: PushPull for [
             PB9<1>,
+
+            #[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+            PD15<2>,
+        ],
+    }
+
+    pin! {
+        <Ext, Input> for [
+            #[cfg(feature = "gpioe")]
+            PE0<3>,
         ],
     }
 
@@ -250,6 +288,9 @@ pub mod tmr4 {
     impl TmrCPin<3> for TMR {
         type Ch<Otype> = Ch4<Otype>;
     }
+    impl TmrExt for TMR {
+        type Ext = Ext;
+    }
 }
 
 #[cfg(feature = "tmr5")]
@@ -260,13 +301,15 @@ pub mod tmr5 {
         <Ch1> default: PushPull for [
             PA0<1>,
 
-            PF4<1>,
+            #[cfg(feature = "gpiof")]
+            PF4<2>,
         ],
 
         <Ch2> default: PushPull for [
             PA1<1>,
 
-            PF5<1>,
+            #[cfg(feature = "gpiof")]
+            PF5<2>,
         ],
 
         <Ch3> default: PushPull for [
@@ -275,7 +318,6 @@ pub mod tmr5 {
 
         <Ch4> default: PushPull for [
             PA3<1>,
-
         ],
     }
 
@@ -295,6 +337,81 @@ pub mod tmr5 {
     }
 }
 
+#[cfg(feature = "tmr8")]
+pub mod tmr8 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PC6<1>,
+        ],
+
+        <Ch1N> default: PushPull for [
+            PA7<1>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PC7<1>,
+        ],
+
+        <Ch2N> default: PushPull for [
+            PB0<1>,
+        ],
+
+        <Ch3> default: PushPull for [
+            PC8<1>,
+        ],
+
+        <Ch3N> default: PushPull for [
+            PB1<1>,
+        ],
+
+        <Ch4> default: PushPull for [
+            PC9<1>,
+        ],
+    }
+
+    pin! {
+        <Bkin, Input> for [
+            PA6<1>,
+        ],
+
+        <Ext, Input> for [
+            PA0<1>,
+        ],
+    }
+
+    use crate::pac::TMR8 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+    impl TmrCPin<2> for TMR {
+        type Ch<Otype> = Ch3<Otype>;
+    }
+    impl TmrCPin<3> for TMR {
+        type Ch<Otype> = Ch4<Otype>;
+    }
+    impl TmrNCPin<0> for TMR {
+        type ChN<Otype> = Ch1N<Otype>;
+    }
+    impl TmrNCPin<1> for TMR {
+        type ChN<Otype> = Ch2N<Otype>;
+    }
+    impl TmrNCPin<2> for TMR {
+        type ChN<Otype> = Ch3N<Otype>;
+    }
+    impl TmrBkin for TMR {
+        type Bkin = Bkin;
+    }
+    impl TmrExt for TMR {
+        type Ext = Ext;
+    }
+}
+
 #[cfg(feature = "tmr9")]
 pub mod tmr9 {
     use super::*;
@@ -303,15 +420,22 @@ pub mod tmr9 {
         <Ch1> default: PushPull for [
             PA2<1>,
 
-            PB14<1>,
+            #[cfg(feature = "f415-peripheral")]
+            PB14<2>,
+
+            #[cfg(feature = "gpioe")]
+            PE5<2>,
         ],
 
         <Ch2> default: PushPull for [
             PA3<1>,
 
-            PB15<1>,
-        ],
+            #[cfg(feature = "f415-peripheral")]
+            PB15<2>,
 
+            #[cfg(feature = "gpioe")]
+            PE6<2>,
+        ],
     }
 
     use crate::pac::TMR9 as TMR;
@@ -324,20 +448,112 @@ pub mod tmr9 {
     }
 }
 
+#[cfg(feature = "tmr10")]
+pub mod tmr10 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PB8<1>,
+        ],
+    }
+
+    use crate::pac::TMR10 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr11")]
+pub mod tmr11 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PB9<1>,
+        ],
+    }
+
+    use crate::pac::TMR11 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr12")]
+pub mod tmr12 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PB14<1>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PB15<1>,
+        ],
+    }
+
+    use crate::pac::TMR12 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr13")]
+pub mod tmr13 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA6<1>,
+        ],
+    }
+
+    use crate::pac::TMR13 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr14")]
+pub mod tmr14 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA7<1>,
+        ],
+    }
+
+    use crate::pac::TMR14 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+}
+
 pub mod usart1 {
     use super::*;
 
     pin! {
         <Ck, PushPull> for [
-            PA8<1>,
+            PA8<3>,
         ],
 
         <Cts, PushPull> for [
-            PA11<1>,
+            PA11<3>,
         ],
 
         <Rts, PushPull> for [
-            PA12<1>,
+            PA12<3>,
         ],
     }
 
@@ -345,17 +561,18 @@ pub mod usart1 {
         <Rx> default: PushPull for no:NoPin, [
             PA10<1>,
 
-            PB7<1>,
+            PB7<2>,
         ],
 
         <Tx> default: PushPull for no:NoPin, [
             PA9<1>,
 
-            PB6<1>,
+            PB6<2>,
         ],
     }
 
     use crate::pac::USART1 as USART;
+
     impl SerialAsync for USART {
         type Rx<Otype> = Rx<Otype>;
         type Tx<Otype> = Tx<Otype>;
@@ -375,28 +592,44 @@ pub mod usart2 {
     pin! {
         <Ck, PushPull> for [
             PA4<1>,
+
+            #[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+            PD7<2>,
         ],
 
         <Cts, PushPull> for [
             PA0<1>,
+
+            #[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+            PD3<2>,
         ],
 
         <Rts, PushPull> for [
             PA1<1>,
+
+            #[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+            PD4<2>,
         ],
     }
 
     pin! {
         <Rx> default: PushPull for no:NoPin, [
             PA3<1>,
+
+            #[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+            PD6<2>,
         ],
 
         <Tx> default: PushPull for no:NoPin, [
             PA2<1>,
+
+            #[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+            PD5<2>,
         ],
     }
 
     use crate::pac::USART2 as USART;
+
     impl SerialAsync for USART {
         type Rx<Otype> = Rx<Otype>;
         type Tx<Otype> = Tx<Otype>;
@@ -417,28 +650,50 @@ pub mod usart3 {
     pin! {
         <Ck, PushPull> for [
             PB12<1>,
+
+            PC12<2>,
+
+            #[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+            PD10<8>,
         ],
 
         <Cts, PushPull> for [
-            PB13<1>,
+            PB13<3>,
+
+            #[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+            PD11<8>,
         ],
 
         <Rts, PushPull> for [
-            PB14<1>,
+            PB14<3>,
+
+            #[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+            PD12<8>,
         ],
     }
 
     pin! {
         <Rx> default: PushPull for no:NoPin, [
             PB11<1>,
+
+            PC11<2>,
+
+            #[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+            PD9<8>,
         ],
 
         <Tx> default: PushPull for no:NoPin, [
             PB10<1>,
+
+            PC10<2>,
+
+            #[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+            PD8<8>,
         ],
     }
 
     use crate::pac::USART3 as USART;
+
     impl SerialAsync for USART {
         type Rx<Otype> = Rx<Otype>;
         type Tx<Otype> = Tx<Otype>;
@@ -452,21 +707,63 @@ pub mod usart3 {
     }
 }
 
+#[cfg(feature = "usart6")]
+pub mod usart6 {
+    use super::*;
+
+    pin! {
+        <Ck, PushPull> for [
+            PC8<1>,
+        ],
+    }
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PA5<2>,
+
+            PC7<1>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PA4<2>,
+
+            PC6<1>,
+        ],
+    }
+
+    use crate::pac::USART6 as USART;
+
+    impl SerialAsync for USART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+    impl SerialSync for USART {
+        type Ck = Ck;
+    }
+}
+
 #[cfg(feature = "uart4")]
 pub mod uart4 {
     use super::*;
+
     pin! {
         <Rx> default: PushPull for no:NoPin, [
-            PC11<0>,
-            PF5<1>,
+            PC11<1>,
+
+            #[cfg(feature = "gpiof")]
+            PF5<2>,
         ],
+
         <Tx> default: PushPull for no:NoPin, [
-            PC10<0>,
-            PF4<1>,
+            PC10<1>,
+
+            #[cfg(feature = "gpiof")]
+            PF4<2>,
         ],
     }
 
     use crate::pac::UART4 as UART;
+
     impl SerialAsync for UART {
         type Rx<Otype> = Rx<Otype>;
         type Tx<Otype> = Tx<Otype>;
@@ -476,18 +773,368 @@ pub mod uart4 {
 #[cfg(feature = "uart5")]
 pub mod uart5 {
     use super::*;
+
     pin! {
         <Rx> default: PushPull for no:NoPin, [
-            PD2<0>,
+            #[cfg(feature = "f403a-peripheral")]
+            PB8<2>,
+
+            PD2<1>,
         ],
+
         <Tx> default: PushPull for no:NoPin, [
-            PC12<0>,
+            #[cfg(feature = "f403a-peripheral")]
+            PB9<2>,
+
+            PC12<1>,
         ],
     }
 
     use crate::pac::UART5 as UART;
+
     impl SerialAsync for UART {
         type Rx<Otype> = Rx<Otype>;
         type Tx<Otype> = Tx<Otype>;
     }
 }
+
+#[cfg(feature = "uart7")]
+pub mod uart7 {
+    use super::*;
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PB3<2>,
+
+            PE7<1>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PB4<2>,
+
+            PE8<1>,
+        ],
+    }
+
+    use crate::pac::UART7 as UART;
+
+    impl SerialAsync for UART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+}
+
+#[cfg(feature = "uart8")]
+pub mod uart8 {
+    use super::*;
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PC3<2>,
+
+            PE0<1>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PC2<2>,
+
+            PE1<1>,
+        ],
+    }
+
+    use crate::pac::UART8 as UART;
+
+    impl SerialAsync for UART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+}
+
+pub mod spi1 {
+    use super::*;
+
+    pin! {
+        <Miso, PushPull> for no:NoPin, [
+            PA6<1>,
+
+            PB4<2>,
+        ],
+
+        <Mosi, PushPull> for no:NoPin, [
+            PA7<1>,
+
+            PB5<2>,
+        ],
+
+        <Nss, PushPull> for [
+            PA4<1>,
+
+            PA15<2>,
+        ],
+
+        <Sck, PushPull> for no:NoPin, [
+            PA5<1>,
+
+            PB3<2>,
+        ],
+    }
+
+    use crate::pac::SPI1 as SPI;
+
+    impl SpiCommon for SPI {
+        type Miso = Miso;
+        type Mosi = Mosi;
+        type Nss = Nss;
+        type Sck = Sck;
+    }
+}
+
+pub mod spi2 {
+    use super::*;
+
+    pin! {
+        <Miso, PushPull> for no:NoPin, [
+            PB14<1>,
+        ],
+
+        <Mosi, PushPull> for no:NoPin, [
+            PB15<1>,
+        ],
+
+        <Nss, PushPull> for [
+            PB12<1>,
+        ],
+
+        <Sck, PushPull> for no:NoPin, [
+            PB13<1>,
+        ],
+    }
+
+    use crate::pac::SPI2 as SPI;
+
+    impl SpiCommon for SPI {
+        type Miso = Miso;
+        type Mosi = Mosi;
+        type Nss = Nss;
+        type Sck = Sck;
+    }
+}
+
+#[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+pub mod spi3 {
+    use super::*;
+
+    pin! {
+        <Miso, PushPull> for no:NoPin, [
+            PB4<1>,
+
+            PC11<2>,
+        ],
+
+        <Mosi, PushPull> for no:NoPin, [
+            PB5<1>,
+
+            PC12<2>,
+        ],
+
+        <Nss, PushPull> for [
+            PA4<2>,
+
+            PA15<1>,
+        ],
+
+        <Sck, PushPull> for no:NoPin, [
+            PB3<1>,
+
+            PC10<2>,
+        ],
+    }
+
+    use crate::pac::SPI3 as SPI;
+
+    impl SpiCommon for SPI {
+        type Miso = Miso;
+        type Mosi = Mosi;
+        type Nss = Nss;
+        type Sck = Sck;
+    }
+}
+
+#[cfg(any(feature = "f403-peripheral", feature = "f403a-peripheral"))]
+pub mod spi4 {
+    use super::*;
+
+    pin! {
+        <Miso, PushPull> for no:NoPin, [
+            PE5<1>,
+
+            PE13<2>,
+        ],
+
+        <Mosi, PushPull> for no:NoPin, [
+            PE6<1>,
+
+            PE14<2>,
+        ],
+
+        <Nss, PushPull> for [
+            PE4<1>,
+
+            PE12<2>,
+        ],
+
+        <Sck, PushPull> for no:NoPin, [
+            PE2<1>,
+
+            PE11<2>,
+        ],
+    }
+
+    use crate::pac::SPI4 as SPI;
+
+    impl SpiCommon for SPI {
+        type Miso = Miso;
+        type Mosi = Mosi;
+        type Nss = Nss;
+        type Sck = Sck;
+    }
+}
+
+pub mod i2c1 {
+    use super::*;
+
+    pin! {
+        <Scl, OpenDrain> for [
+            PB6<1>,
+
+            PB8<2>,
+        ],
+
+        <Sda, OpenDrain> for [
+            PB7<1>,
+
+            PB9<2>,
+        ],
+
+        <Smba, OpenDrain> for [
+            PB5<3>,
+        ],
+    }
+
+    use crate::pac::I2C1 as I2C;
+
+    impl I2cCommon for I2C {
+        type Scl = Scl;
+        type Sda = Sda;
+        type Smba = Smba;
+    }
+}
+
+pub mod i2c2 {
+    use super::*;
+
+    pin! {
+        <Scl, OpenDrain> for [
+            PB10<1>,
+        ],
+
+        <Sda, OpenDrain> for [
+            PB11<1>,
+        ],
+
+        <Smba, OpenDrain> for [
+            PB12<1>,
+        ],
+    }
+
+    use crate::pac::I2C2 as I2C;
+
+    impl I2cCommon for I2C {
+        type Scl = Scl;
+        type Sda = Sda;
+        type Smba = Smba;
+    }
+}
+
+#[cfg(feature = "i2c3")]
+pub mod i2c3 {
+    use super::*;
+
+    pin! {
+        <Scl, OpenDrain> for [
+            PA8<1>,
+        ],
+
+        <Sda, OpenDrain> for [
+            PC9<1>,
+        ],
+
+        <Smba, OpenDrain> for [
+            PA9<1>,
+        ],
+    }
+
+    use crate::pac::I2C3 as I2C;
+
+    impl I2cCommon for I2C {
+        type Scl = Scl;
+        type Sda = Sda;
+        type Smba = Smba;
+    }
+}
+
+#[cfg(feature = "can1")]
+pub mod can1 {
+    use super::*;
+
+    pin! {
+        <Rx, PushPull> for [
+            PA11<1>,
+
+            PB8<4>,
+
+            PD0<8>,
+        ],
+
+        <Tx, PushPull> for [
+            PA12<1>,
+
+            PB9<4>,
+
+            PD1<8>,
+        ],
+    }
+
+    use crate::pac::CAN1 as CAN;
+
+    impl CanCommon for CAN {
+        type Rx = Rx;
+        type Tx = Tx;
+    }
+}
+
+#[cfg(feature = "can2")]
+pub mod can2 {
+    use super::*;
+
+    pin! {
+        <Rx, PushPull> for [
+            PB5<2>,
+
+            PB12<1>,
+        ],
+
+        <Tx, PushPull> for [
+            PB6<2>,
+
+            PB13<1>,
+        ],
+    }
+
+    use crate::pac::CAN2 as CAN;
+
+    impl CanCommon for CAN {
+        type Rx = Rx;
+        type Tx = Tx;
+    }
+}
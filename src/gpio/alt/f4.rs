@@ -1,5 +1,5 @@
 use super::*;
-use crate::gpio::{self, NoPin, OpenDrain, PushPull};
+use crate::gpio::{self, Input, NoPin, OpenDrain, PushPull};
 
 #[cfg(feature = "tmr1")]
 pub mod tmr1 {
@@ -44,13 +44,13 @@ pub mod tmr1 {
     }
 
     pin! {
-        <Bkin, PushPull> for [
+        <Bkin, Input> for [
             PA6<2>,
 
             PB12<2>,
         ],
 
-        <Ext, PushPull> for [
+        <Ext, Input> for [
             PA0<5>,
 
             PA12<2>,
@@ -88,48 +88,33 @@ pub mod tmr1 {
     }
 }
 
-#[cfg(feature = "tmr2")]
-pub mod tmr2 {
+#[cfg(feature = "tmr3")]
+pub mod tmr3 {
     use super::*;
 
     pin! {
         <Ch1> default: PushPull for [
-            PA0<1>,
-
-            PA15<1>,
+            PA6<1>,
 
+            PB4<1>,
         ],
 
         <Ch2> default: PushPull for [
-            PA1<1>,
-
-            PB3<1>,
+            PA7<1>,
 
+            PB5<1>,
         ],
 
         <Ch3> default: PushPull for [
-            PA2<1>,
-
-            PB10<1>,
+            PB0<1>,
         ],
 
         <Ch4> default: PushPull for [
-            PA3<1>,
-
-            PB11<1>,
-        ],
-    }
-
-    pin! {
-        <Ext, PushPull> for [
-            PA0<1>,
-
-            PA15<1>,
-
+            PB1<1>,
         ],
     }
 
-    use crate::pac::TMR2 as TMR;
+    use crate::pac::TMR3 as TMR;
 
     impl TmrCPin<0> for TMR {
         type Ch<Otype> = Ch1<Otype>;
@@ -143,93 +128,62 @@ pub mod tmr2 {
     impl TmrCPin<3> for TMR {
         type Ch<Otype> = Ch4<Otype>;
     }
-    impl TmrExt for TMR {
-        type Ext = Ext;
-    }
 }
 
-#[cfg(feature = "tmr3")]
-pub mod tmr3 {
+#[cfg(feature = "tmr14")]
+pub mod tmr14 {
     use super::*;
 
     pin! {
         <Ch1> default: PushPull for [
-            PA6<1>,
+            PA4<4>,
 
-            PB4<1>,
+            PA7<4>,
 
-            PC6<1>,
-        ],
-
-        <Ch2> default: PushPull for [
-            PA7<1>,
-
-            PB5<1>,
-
-            PC7<1>,
-        ],
-
-        <Ch3> default: PushPull for [
-            PB0<1>,
-
-            PC8<1>,
-        ],
-
-        <Ch4> default: PushPull for [
-            PB1<1>,
-
-            PC9<1>,
-        ],
-    }
-
-    pin! {
-        <Ext, PushPull> for [
-            PD2<1>,
+            PB1<0>,
         ],
     }
 
-    use crate::pac::TMR3 as TMR;
+    use crate::pac::TMR14 as TMR;
 
     impl TmrCPin<0> for TMR {
         type Ch<Otype> = Ch1<Otype>;
     }
-    impl TmrCPin<1> for TMR {
-        type Ch<Otype> = Ch2<Otype>;
-    }
-    impl TmrCPin<2> for TMR {
-        type Ch<Otype> = Ch3<Otype>;
-    }
-    impl TmrCPin<3> for TMR {
-        type Ch<Otype> = Ch4<Otype>;
-    }
-    impl TmrExt for TMR {
-        type Ext = Ext;
-    }
 }
 
-#[cfg(feature = "tmr4")]
-pub mod tmr4 {
+#[cfg(feature = "tmr15")]
+pub mod tmr15 {
     use super::*;
 
     pin! {
         <Ch1> default: PushPull for [
-            PB6<1>,
+            PA2<0>,
+
+            PB14<1>,
         ],
 
-        <Ch2> default: PushPull for [
-            PB7<1>,
+        <Ch1N> default: PushPull for [
+            PA1<5>,
+
+            PB15<3>,
         ],
 
-        <Ch3> default: PushPull for [
-            PB8<1>,
+        <Ch2> default: PushPull for [
+            PA3<0>,
+
+            PB15<1>,
         ],
+    }
 
-        <Ch4> default: PushPull for [
-            PB9<1>,
+    pin! {
+        <Bkin, Input> for [
+            PA9<0>,
+
+            PB12<5>,
         ],
     }
 
-    use crate::pac::TMR4 as TMR;
+    use crate::pac::TMR15 as TMR;
 
     impl TmrCPin<0> for TMR {
         type Ch<Otype> = Ch1<Otype>;
@@ -237,83 +191,83 @@ pub mod tmr4 {
     impl TmrCPin<1> for TMR {
         type Ch<Otype> = Ch2<Otype>;
     }
-    impl TmrCPin<2> for TMR {
-        type Ch<Otype> = Ch3<Otype>;
+    impl TmrNCPin<0> for TMR {
+        type ChN<Otype> = Ch1N<Otype>;
     }
-    impl TmrCPin<3> for TMR {
-        type Ch<Otype> = Ch4<Otype>;
+    impl TmrBkin for TMR {
+        type Bkin = Bkin;
     }
 }
 
-#[cfg(feature = "tmr5")]
-pub mod tmr5 {
+#[cfg(feature = "tmr16")]
+pub mod tmr16 {
     use super::*;
 
     pin! {
         <Ch1> default: PushPull for [
-            PA0<1>,
+            PA6<5>,
 
-            PF4<1>,
+            PB8<2>,
         ],
 
-        <Ch2> default: PushPull for [
-            PA1<1>,
-
-            PF5<1>,
-        ],
-
-        <Ch3> default: PushPull for [
-            PA2<1>,
+        <Ch1N> default: PushPull for [
+            PB6<2>,
         ],
+    }
 
-        <Ch4> default: PushPull for [
-            PA3<1>,
-
+    pin! {
+        <Bkin, Input> for [
+            PB5<2>,
         ],
     }
 
-    use crate::pac::TMR5 as TMR;
+    use crate::pac::TMR16 as TMR;
 
     impl TmrCPin<0> for TMR {
         type Ch<Otype> = Ch1<Otype>;
     }
-    impl TmrCPin<1> for TMR {
-        type Ch<Otype> = Ch2<Otype>;
-    }
-    impl TmrCPin<2> for TMR {
-        type Ch<Otype> = Ch3<Otype>;
+    impl TmrNCPin<0> for TMR {
+        type ChN<Otype> = Ch1N<Otype>;
     }
-    impl TmrCPin<3> for TMR {
-        type Ch<Otype> = Ch4<Otype>;
+    impl TmrBkin for TMR {
+        type Bkin = Bkin;
     }
 }
 
-#[cfg(feature = "tmr9")]
-pub mod tmr9 {
+#[cfg(feature = "tmr17")]
+pub mod tmr17 {
     use super::*;
 
     pin! {
         <Ch1> default: PushPull for [
-            PA2<1>,
+            PA7<5>,
 
-            PB14<1>,
+            PB9<2>,
         ],
 
-        <Ch2> default: PushPull for [
-            PA3<1>,
-
-            PB15<1>,
+        <Ch1N> default: PushPull for [
+            PB7<2>,
         ],
+    }
+
+    pin! {
+        <Bkin, Input> for [
+            PA10<0>,
 
+            PB4<5>,
+        ],
     }
 
-    use crate::pac::TMR9 as TMR;
+    use crate::pac::TMR17 as TMR;
 
     impl TmrCPin<0> for TMR {
         type Ch<Otype> = Ch1<Otype>;
     }
-    impl TmrCPin<1> for TMR {
-        type Ch<Otype> = Ch2<Otype>;
+    impl TmrNCPin<0> for TMR {
+        type ChN<Otype> = Ch1N<Otype>;
+    }
+    impl TmrBkin for TMR {
+        type Bkin = Bkin;
     }
 }
 
@@ -0,0 +1,791 @@
+use super::*;
+use crate::gpio::{self, Input, NoPin, OpenDrain, PushPull};
+
+#[cfg(feature = "tmr1")]
+pub mod tmr1 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA8<1>,
+        ],
+
+        <Ch1N> default: PushPull for [
+            PA7<1>,
+
+            PB13<1>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PA9<1>,
+        ],
+
+        <Ch2N> default: PushPull for [
+            PB0<1>,
+
+            PB14<1>,
+        ],
+
+        <Ch3> default: PushPull for [
+            PA10<1>,
+        ],
+
+        <Ch3N> default: PushPull for [
+            PB1<1>,
+
+            PB15<1>,
+        ],
+
+        <Ch4> default: PushPull for [
+            PA11<1>,
+        ],
+    }
+
+    pin! {
+        <Bkin, Input> for [
+            PA6<1>,
+
+            PB12<1>,
+        ],
+
+        <Ext, Input> for [
+            PA12<1>,
+        ],
+    }
+
+    use crate::pac::TMR1 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+    impl TmrCPin<2> for TMR {
+        type Ch<Otype> = Ch3<Otype>;
+    }
+    impl TmrCPin<3> for TMR {
+        type Ch<Otype> = Ch4<Otype>;
+    }
+    impl TmrNCPin<0> for TMR {
+        type ChN<Otype> = Ch1N<Otype>;
+    }
+    impl TmrNCPin<1> for TMR {
+        type ChN<Otype> = Ch2N<Otype>;
+    }
+    impl TmrNCPin<2> for TMR {
+        type ChN<Otype> = Ch3N<Otype>;
+    }
+    impl TmrBkin for TMR {
+        type Bkin = Bkin;
+    }
+    impl TmrExt for TMR {
+        type Ext = Ext;
+    }
+}
+
+#[cfg(feature = "tmr2")]
+pub mod tmr2 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA0<1>,
+
+            PA5<1>,
+
+            PA15<1>,
+
+            PB8<1>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PA1<1>,
+
+            PB3<1>,
+
+            PB9<1>,
+        ],
+
+        <Ch3> default: PushPull for [
+            PA2<1>,
+
+            PB10<1>,
+        ],
+
+        <Ch4> default: PushPull for [
+            PA3<1>,
+
+            PB2<1>,
+
+            PB11<1>,
+        ],
+    }
+
+    pin! {
+        <Ext, Input> for [
+            PA0<1>,
+
+            PA5<1>,
+
+            PA15<1>,
+        ],
+    }
+
+    use crate::pac::TMR2 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+    impl TmrCPin<2> for TMR {
+        type Ch<Otype> = Ch3<Otype>;
+    }
+    impl TmrCPin<3> for TMR {
+        type Ch<Otype> = Ch4<Otype>;
+    }
+    impl TmrExt for TMR {
+        type Ext = Ext;
+    }
+}
+
+#[cfg(feature = "tmr3")]
+pub mod tmr3 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA6<2>,
+
+            PB4<2>,
+
+            PC6<2>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PA7<2>,
+
+            PB5<2>,
+
+            PC7<2>,
+        ],
+
+        <Ch3> default: PushPull for [
+            PB0<2>,
+
+            PC8<2>,
+        ],
+
+        <Ch4> default: PushPull for [
+            PB1<2>,
+
+            PC9<2>,
+        ],
+    }
+
+    pin! {
+        <Ext, Input> for [
+            PD2<2>,
+        ],
+    }
+
+    use crate::pac::TMR3 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+    impl TmrCPin<2> for TMR {
+        type Ch<Otype> = Ch3<Otype>;
+    }
+    impl TmrCPin<3> for TMR {
+        type Ch<Otype> = Ch4<Otype>;
+    }
+    impl TmrExt for TMR {
+        type Ext = Ext;
+    }
+}
+
+#[cfg(feature = "tmr4")]
+pub mod tmr4 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PB6<2>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PB7<2>,
+        ],
+
+        <Ch3> default: PushPull for [
+            PB8<2>,
+        ],
+
+        <Ch4> default: PushPull for [
+            PB9<2>,
+        ],
+    }
+
+    use crate::pac::TMR4 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+    impl TmrCPin<2> for TMR {
+        type Ch<Otype> = Ch3<Otype>;
+    }
+    impl TmrCPin<3> for TMR {
+        type Ch<Otype> = Ch4<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr9")]
+pub mod tmr9 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA2<3>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PA3<3>,
+        ],
+    }
+
+    use crate::pac::TMR9 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr10")]
+pub mod tmr10 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PB8<3>,
+        ],
+    }
+
+    use crate::pac::TMR10 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr11")]
+pub mod tmr11 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PB9<3>,
+        ],
+    }
+
+    use crate::pac::TMR11 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr13")]
+pub mod tmr13 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA6<9>,
+        ],
+    }
+
+    use crate::pac::TMR13 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr14")]
+pub mod tmr14 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA7<9>,
+        ],
+    }
+
+    use crate::pac::TMR14 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+}
+
+pub mod usart1 {
+    use super::*;
+
+    pin! {
+        <Ck, PushPull> for [
+            PA8<7>,
+        ],
+
+        <Cts, PushPull> for [
+            PA11<7>,
+        ],
+
+        <Rts, PushPull> for [
+            PA12<7>,
+        ],
+    }
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PA10<7>,
+
+            PB7<7>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PA9<7>,
+
+            PB6<7>,
+        ],
+    }
+
+    use crate::pac::USART1 as USART;
+
+    impl SerialAsync for USART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+    impl SerialSync for USART {
+        type Ck = Ck;
+    }
+    impl SerialRs232 for USART {
+        type Cts = Cts;
+        type Rts = Rts;
+    }
+}
+
+pub mod usart2 {
+    use super::*;
+
+    pin! {
+        <Ck, PushPull> for [
+            PA4<7>,
+        ],
+
+        <Cts, PushPull> for [
+            PA0<7>,
+        ],
+
+        <Rts, PushPull> for [
+            PA1<7>,
+        ],
+    }
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PA3<7>,
+
+            PA15<7>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PA2<7>,
+
+            PA14<7>,
+        ],
+    }
+
+    use crate::pac::USART2 as USART;
+
+    impl SerialAsync for USART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+    impl SerialSync for USART {
+        type Ck = Ck;
+    }
+    impl SerialRs232 for USART {
+        type Cts = Cts;
+        type Rts = Rts;
+    }
+}
+
+#[cfg(feature = "usart3")]
+pub mod usart3 {
+    use super::*;
+
+    pin! {
+        <Ck, PushPull> for [
+            PB12<7>,
+
+            PC12<7>,
+        ],
+
+        <Cts, PushPull> for [
+            PB13<7>,
+        ],
+
+        <Rts, PushPull> for [
+            PB14<7>,
+        ],
+    }
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PB11<7>,
+
+            PC11<7>,
+
+            PC5<7>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PB10<7>,
+
+            PC10<7>,
+
+            PC4<7>,
+        ],
+    }
+
+    use crate::pac::USART3 as USART;
+
+    impl SerialAsync for USART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+    impl SerialSync for USART {
+        type Ck = Ck;
+    }
+    impl SerialRs232 for USART {
+        type Cts = Cts;
+        type Rts = Rts;
+    }
+}
+
+#[cfg(feature = "usart4")]
+pub mod usart4 {
+    use super::*;
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PA1<8>,
+
+            PC11<8>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PA0<8>,
+
+            PC10<8>,
+        ],
+    }
+
+    use crate::pac::USART4 as USART;
+
+    impl SerialAsync for USART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+}
+
+#[cfg(feature = "uart7")]
+pub mod uart7 {
+    use super::*;
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PB3<8>,
+
+            PC1<8>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PB4<8>,
+
+            PC0<8>,
+        ],
+    }
+
+    use crate::pac::UART7 as UART;
+
+    impl SerialAsync for UART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+}
+
+pub mod spi1 {
+    use super::*;
+
+    pin! {
+        <Miso, PushPull> for no:NoPin, [
+            PA6<5>,
+
+            PB4<5>,
+        ],
+
+        <Mosi, PushPull> for no:NoPin, [
+            PA7<5>,
+
+            PB5<5>,
+        ],
+
+        <Nss, PushPull> for [
+            PA4<5>,
+
+            PA15<5>,
+        ],
+
+        <Sck, PushPull> for no:NoPin, [
+            PA5<5>,
+
+            PB3<5>,
+        ],
+    }
+
+    use crate::pac::SPI1 as SPI;
+
+    impl SpiCommon for SPI {
+        type Miso = Miso;
+        type Mosi = Mosi;
+        type Nss = Nss;
+        type Sck = Sck;
+    }
+}
+
+pub mod spi2 {
+    use super::*;
+
+    pin! {
+        <Miso, PushPull> for no:NoPin, [
+            PB14<5>,
+
+            PC2<5>,
+        ],
+
+        <Mosi, PushPull> for no:NoPin, [
+            PB15<5>,
+
+            PC3<5>,
+        ],
+
+        <Nss, PushPull> for [
+            PB9<5>,
+
+            PB12<5>,
+        ],
+
+        <Sck, PushPull> for no:NoPin, [
+            PB10<5>,
+
+            PB13<5>,
+        ],
+    }
+
+    use crate::pac::SPI2 as SPI;
+
+    impl SpiCommon for SPI {
+        type Miso = Miso;
+        type Mosi = Mosi;
+        type Nss = Nss;
+        type Sck = Sck;
+    }
+}
+
+pub mod spi3 {
+    use super::*;
+
+    pin! {
+        <Miso, PushPull> for no:NoPin, [
+            PB4<6>,
+
+            PC11<6>,
+        ],
+
+        <Mosi, PushPull> for no:NoPin, [
+            PB5<6>,
+
+            PC12<6>,
+        ],
+
+        <Nss, PushPull> for [
+            PA4<6>,
+
+            PA15<6>,
+        ],
+
+        <Sck, PushPull> for no:NoPin, [
+            PB3<6>,
+
+            PC10<6>,
+        ],
+    }
+
+    use crate::pac::SPI3 as SPI;
+
+    impl SpiCommon for SPI {
+        type Miso = Miso;
+        type Mosi = Mosi;
+        type Nss = Nss;
+        type Sck = Sck;
+    }
+}
+
+pub mod i2c1 {
+    use super::*;
+
+    pin! {
+        <Scl, OpenDrain> for [
+            PB6<4>,
+
+            PB8<4>,
+        ],
+
+        <Sda, OpenDrain> for [
+            PB7<4>,
+
+            PB9<4>,
+        ],
+
+        <Smba, OpenDrain> for [
+            PB5<4>,
+        ],
+    }
+
+    use crate::pac::I2C1 as I2C;
+
+    impl I2cCommon for I2C {
+        type Scl = Scl;
+        type Sda = Sda;
+        type Smba = Smba;
+    }
+}
+
+pub mod i2c2 {
+    use super::*;
+
+    pin! {
+        <Scl, OpenDrain> for [
+            PB10<4>,
+
+            PF1<4>,
+        ],
+
+        <Sda, OpenDrain> for [
+            PB11<4>,
+
+            PF0<4>,
+        ],
+
+        <Smba, OpenDrain> for [
+            PB12<4>,
+        ],
+    }
+
+    use crate::pac::I2C2 as I2C;
+
+    impl I2cCommon for I2C {
+        type Scl = Scl;
+        type Sda = Sda;
+        type Smba = Smba;
+    }
+}
+
+#[cfg(feature = "i2c3")]
+pub mod i2c3 {
+    use super::*;
+
+    pin! {
+        <Scl, OpenDrain> for [
+            PA8<4>,
+
+            PC0<4>,
+        ],
+
+        <Sda, OpenDrain> for [
+            PC9<4>,
+
+            PC1<4>,
+        ],
+
+        <Smba, OpenDrain> for [
+            PA9<4>,
+        ],
+    }
+
+    use crate::pac::I2C3 as I2C;
+
+    impl I2cCommon for I2C {
+        type Scl = Scl;
+        type Sda = Sda;
+        type Smba = Smba;
+    }
+}
+
+#[cfg(feature = "can1")]
+pub mod can1 {
+    use super::*;
+
+    pin! {
+        <Rx, PushPull> for [
+            PA11<9>,
+
+            PB8<9>,
+        ],
+
+        <Tx, PushPull> for [
+            PA12<9>,
+
+            PB9<9>,
+        ],
+    }
+
+    use crate::pac::CAN1 as CAN;
+
+    impl CanCommon for CAN {
+        type Rx = Rx;
+        type Tx = Tx;
+    }
+}
+
+#[cfg(feature = "otg1")]
+pub mod otg_fs {
+    use super::*;
+
+    pin! {
+        <Id, PushPull> for [
+            PA10<10>,
+        ],
+
+        <Sof, PushPull> for [
+            PA8<10>,
+        ],
+
+        <Vbus, PushPull> for [
+            PA9<10>,
+        ],
+    }
+}
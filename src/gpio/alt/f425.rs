@@ -0,0 +1,729 @@
+use super::*;
+use crate::gpio::{self, Input, NoPin, OpenDrain, PushPull};
+
+#[cfg(feature = "tmr1")]
+pub mod tmr1 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA8<2>,
+        ],
+
+        <Ch1N> default: PushPull for [
+            PA7<2>,
+
+            PB13<2>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PA9<2>,
+        ],
+
+        <Ch2N> default: PushPull for [
+            PB0<2>,
+
+            PB14<2>,
+        ],
+
+        <Ch3> default: PushPull for [
+            PA10<2>,
+        ],
+
+        <Ch3N> default: PushPull for [
+            PB1<2>,
+
+            PB15<2>,
+        ],
+
+        <Ch4> default: PushPull for [
+            PA11<2>,
+        ],
+    }
+
+    pin! {
+        <Bkin, Input> for [
+            PA6<2>,
+
+            PB12<2>,
+        ],
+
+        <Ext, Input> for [
+            PA12<2>,
+        ],
+    }
+
+    use crate::pac::TMR1 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+    impl TmrCPin<2> for TMR {
+        type Ch<Otype> = Ch3<Otype>;
+    }
+    impl TmrCPin<3> for TMR {
+        type Ch<Otype> = Ch4<Otype>;
+    }
+    impl TmrNCPin<0> for TMR {
+        type ChN<Otype> = Ch1N<Otype>;
+    }
+    impl TmrNCPin<1> for TMR {
+        type ChN<Otype> = Ch2N<Otype>;
+    }
+    impl TmrNCPin<2> for TMR {
+        type ChN<Otype> = Ch3N<Otype>;
+    }
+    impl TmrBkin for TMR {
+        type Bkin = Bkin;
+    }
+    impl TmrExt for TMR {
+        type Ext = Ext;
+    }
+}
+
+#[cfg(feature = "tmr2")]
+pub mod tmr2 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA0<2>,
+
+            PA5<2>,
+
+            PA15<2>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PA1<2>,
+
+            PB3<2>,
+        ],
+
+        <Ch3> default: PushPull for [
+            PA2<2>,
+
+            PB10<2>,
+        ],
+
+        <Ch4> default: PushPull for [
+            PA3<2>,
+
+            PB11<2>,
+        ],
+    }
+
+    pin! {
+        <Ext, Input> for [
+            PA0<2>,
+
+            PA5<2>,
+
+            PA15<2>,
+        ],
+    }
+
+    use crate::pac::TMR2 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+    impl TmrCPin<2> for TMR {
+        type Ch<Otype> = Ch3<Otype>;
+    }
+    impl TmrCPin<3> for TMR {
+        type Ch<Otype> = Ch4<Otype>;
+    }
+    impl TmrExt for TMR {
+        type Ext = Ext;
+    }
+}
+
+#[cfg(feature = "tmr3")]
+pub mod tmr3 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA6<1>,
+
+            PB4<1>,
+
+            PC6<0>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PA7<1>,
+
+            PB5<1>,
+
+            PC7<0>,
+        ],
+
+        <Ch3> default: PushPull for [
+            PB0<1>,
+
+            PC8<0>,
+        ],
+
+        <Ch4> default: PushPull for [
+            PB1<1>,
+
+            PC9<0>,
+        ],
+    }
+
+    pin! {
+        <Ext, Input> for [
+            PD2<0>,
+        ],
+    }
+
+    use crate::pac::TMR3 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+    impl TmrCPin<2> for TMR {
+        type Ch<Otype> = Ch3<Otype>;
+    }
+    impl TmrCPin<3> for TMR {
+        type Ch<Otype> = Ch4<Otype>;
+    }
+    impl TmrExt for TMR {
+        type Ext = Ext;
+    }
+}
+
+#[cfg(feature = "tmr13")]
+pub mod tmr13 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA6<4>,
+        ],
+    }
+
+    use crate::pac::TMR13 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr14")]
+pub mod tmr14 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA4<4>,
+
+            PA7<4>,
+
+            PB1<0>,
+        ],
+    }
+
+    use crate::pac::TMR14 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr15")]
+pub mod tmr15 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA2<0>,
+
+            PB14<1>,
+        ],
+
+        <Ch1N> default: PushPull for [
+            PB15<3>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PA3<0>,
+
+            PB15<1>,
+        ],
+    }
+
+    pin! {
+        <Bkin, Input> for [
+            PA9<0>,
+
+            PB12<5>,
+        ],
+    }
+
+    use crate::pac::TMR15 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+    impl TmrNCPin<0> for TMR {
+        type ChN<Otype> = Ch1N<Otype>;
+    }
+    impl TmrBkin for TMR {
+        type Bkin = Bkin;
+    }
+}
+
+#[cfg(feature = "tmr16")]
+pub mod tmr16 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA6<5>,
+
+            PB8<2>,
+        ],
+
+        <Ch1N> default: PushPull for [
+            PB6<2>,
+        ],
+    }
+
+    pin! {
+        <Bkin, Input> for [
+            PB5<2>,
+        ],
+    }
+
+    use crate::pac::TMR16 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrNCPin<0> for TMR {
+        type ChN<Otype> = Ch1N<Otype>;
+    }
+    impl TmrBkin for TMR {
+        type Bkin = Bkin;
+    }
+}
+
+#[cfg(feature = "tmr17")]
+pub mod tmr17 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA7<5>,
+
+            PB9<2>,
+        ],
+
+        <Ch1N> default: PushPull for [
+            PB7<2>,
+        ],
+    }
+
+    pin! {
+        <Bkin, Input> for [
+            PA10<0>,
+
+            PB4<5>,
+        ],
+    }
+
+    use crate::pac::TMR17 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrNCPin<0> for TMR {
+        type ChN<Otype> = Ch1N<Otype>;
+    }
+    impl TmrBkin for TMR {
+        type Bkin = Bkin;
+    }
+}
+
+pub mod usart1 {
+    use super::*;
+
+    pin! {
+        <Ck, PushPull> for [
+            PA8<1>,
+        ],
+
+        <Cts, PushPull> for [
+            PA11<1>,
+        ],
+
+        <Rts, PushPull> for [
+            PA12<1>,
+        ],
+    }
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PA10<1>,
+
+            PB7<0>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PA9<1>,
+
+            PB6<0>,
+        ],
+    }
+
+    use crate::pac::USART1 as USART;
+
+    impl SerialAsync for USART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+    impl SerialSync for USART {
+        type Ck = Ck;
+    }
+    impl SerialRs232 for USART {
+        type Cts = Cts;
+        type Rts = Rts;
+    }
+}
+
+pub mod usart2 {
+    use super::*;
+
+    pin! {
+        <Ck, PushPull> for [
+            PA4<1>,
+        ],
+
+        <Cts, PushPull> for [
+            PA0<1>,
+        ],
+
+        <Rts, PushPull> for [
+            PA1<1>,
+        ],
+    }
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PA3<1>,
+
+            PA15<1>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PA2<1>,
+
+            PA14<1>,
+        ],
+    }
+
+    use crate::pac::USART2 as USART;
+
+    impl SerialAsync for USART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+    impl SerialSync for USART {
+        type Ck = Ck;
+    }
+    impl SerialRs232 for USART {
+        type Cts = Cts;
+        type Rts = Rts;
+    }
+}
+
+#[cfg(feature = "usart3")]
+pub mod usart3 {
+    use super::*;
+
+    pin! {
+        <Ck, PushPull> for [
+            PB0<4>,
+
+            PB12<4>,
+
+            PC12<1>,
+        ],
+
+        <Cts, PushPull> for [
+            PB13<4>,
+        ],
+
+        <Rts, PushPull> for [
+            PB1<4>,
+
+            PB14<4>,
+        ],
+    }
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PB11<4>,
+
+            PC5<1>,
+
+            PC11<1>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PB10<4>,
+
+            PC4<1>,
+
+            PC10<1>,
+        ],
+    }
+
+    use crate::pac::USART3 as USART;
+
+    impl SerialAsync for USART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+    impl SerialSync for USART {
+        type Ck = Ck;
+    }
+    impl SerialRs232 for USART {
+        type Cts = Cts;
+        type Rts = Rts;
+    }
+}
+
+#[cfg(feature = "usart4")]
+pub mod usart4 {
+    use super::*;
+
+    pin! {
+        <Ck, PushPull> for [
+            PC12<0>,
+        ],
+
+        <Cts, PushPull> for [
+            PB7<4>,
+        ],
+
+        <Rts, PushPull> for [
+            PA15<4>,
+        ],
+    }
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PA1<4>,
+
+            PC11<0>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PA0<4>,
+
+            PC10<0>,
+        ],
+    }
+
+    use crate::pac::USART4 as USART;
+
+    impl SerialAsync for USART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+    impl SerialSync for USART {
+        type Ck = Ck;
+    }
+    impl SerialRs232 for USART {
+        type Cts = Cts;
+        type Rts = Rts;
+    }
+}
+
+pub mod spi1 {
+    use super::*;
+
+    pin! {
+        <Miso, PushPull> for no:NoPin, [
+            PA6<0>,
+
+            PB4<0>,
+        ],
+
+        <Mosi, PushPull> for no:NoPin, [
+            PA7<0>,
+
+            PB5<0>,
+        ],
+
+        <Nss, PushPull> for [
+            PA4<0>,
+
+            PA15<0>,
+        ],
+
+        <Sck, PushPull> for no:NoPin, [
+            PA5<0>,
+
+            PB3<0>,
+        ],
+    }
+
+    use crate::pac::SPI1 as SPI;
+
+    impl SpiCommon for SPI {
+        type Miso = Miso;
+        type Mosi = Mosi;
+        type Nss = Nss;
+        type Sck = Sck;
+    }
+}
+
+pub mod spi2 {
+    use super::*;
+
+    pin! {
+        <Miso, PushPull> for no:NoPin, [
+            PB14<0>,
+
+            PC2<1>,
+        ],
+
+        <Mosi, PushPull> for no:NoPin, [
+            PB15<0>,
+
+            PC3<1>,
+        ],
+
+        <Nss, PushPull> for [
+            PB9<5>,
+
+            PB12<0>,
+        ],
+
+        <Sck, PushPull> for no:NoPin, [
+            PB10<5>,
+
+            PB13<0>,
+        ],
+    }
+
+    use crate::pac::SPI2 as SPI;
+
+    impl SpiCommon for SPI {
+        type Miso = Miso;
+        type Mosi = Mosi;
+        type Nss = Nss;
+        type Sck = Sck;
+    }
+}
+
+pub mod i2c1 {
+    use super::*;
+
+    pin! {
+        <Scl, OpenDrain> for [
+            PA9<4>,
+
+            PB6<1>,
+
+            PB8<1>,
+        ],
+
+        <Sda, OpenDrain> for [
+            PA10<4>,
+
+            PB7<1>,
+
+            PB9<1>,
+        ],
+
+        <Smba, OpenDrain> for [
+            PB5<3>,
+        ],
+    }
+
+    use crate::pac::I2C1 as I2C;
+
+    impl I2cCommon for I2C {
+        type Scl = Scl;
+        type Sda = Sda;
+        type Smba = Smba;
+    }
+}
+
+pub mod i2c2 {
+    use super::*;
+
+    pin! {
+        <Scl, OpenDrain> for [
+            PA11<5>,
+
+            PB10<1>,
+
+            PB13<5>,
+        ],
+
+        <Sda, OpenDrain> for [
+            PA12<5>,
+
+            PB11<1>,
+
+            PB14<5>,
+        ],
+
+        <Smba, OpenDrain> for [
+            PB12<3>,
+        ],
+    }
+
+    use crate::pac::I2C2 as I2C;
+
+    impl I2cCommon for I2C {
+        type Scl = Scl;
+        type Sda = Sda;
+        type Smba = Smba;
+    }
+}
+
+#[cfg(feature = "can1")]
+pub mod can1 {
+    use super::*;
+
+    pin! {
+        <Rx, PushPull> for [
+            PA11<4>,
+
+            PB8<4>,
+        ],
+
+        <Tx, PushPull> for [
+            PA12<4>,
+
+            PB9<4>,
+        ],
+    }
+
+    use crate::pac::CAN1 as CAN;
+
+    impl CanCommon for CAN {
+        type Rx = Rx;
+        type Tx = Tx;
+    }
+}
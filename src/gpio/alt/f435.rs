@@ -0,0 +1,1700 @@
+use super::*;
+use crate::gpio::{self, Input, NoPin, OpenDrain, PushPull};
+
+#[cfg(feature = "tmr1")]
+pub mod tmr1 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA8<1>,
+
+            PE9<1>,
+        ],
+
+        <Ch1N> default: PushPull for [
+            PA7<1>,
+
+            PB13<1>,
+
+            PE8<1>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PA9<1>,
+
+            PE11<1>,
+        ],
+
+        <Ch2N> default: PushPull for [
+            PB0<1>,
+
+            PB14<1>,
+
+            PE10<1>,
+        ],
+
+        <Ch3> default: PushPull for [
+            PA10<1>,
+
+            PE13<1>,
+        ],
+
+        <Ch3N> default: PushPull for [
+            PB1<1>,
+
+            PB15<1>,
+
+            PE12<1>,
+        ],
+
+        <Ch4> default: PushPull for [
+            PA11<1>,
+
+            PE14<1>,
+        ],
+    }
+
+    pin! {
+        <Bkin, Input> for [
+            PA6<1>,
+
+            PB12<1>,
+
+            PE15<1>,
+        ],
+
+        <Ext, Input> for [
+            PA12<1>,
+
+            PE7<1>,
+        ],
+    }
+
+    use crate::pac::TMR1 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+    impl TmrCPin<2> for TMR {
+        type Ch<Otype> = Ch3<Otype>;
+    }
+    impl TmrCPin<3> for TMR {
+        type Ch<Otype> = Ch4<Otype>;
+    }
+    impl TmrNCPin<0> for TMR {
+        type ChN<Otype> = Ch1N<Otype>;
+    }
+    impl TmrNCPin<1> for TMR {
+        type ChN<Otype> = Ch2N<Otype>;
+    }
+    impl TmrNCPin<2> for TMR {
+        type ChN<Otype> = Ch3N<Otype>;
+    }
+    impl TmrBkin for TMR {
+        type Bkin = Bkin;
+    }
+    impl TmrExt for TMR {
+        type Ext = Ext;
+    }
+}
+
+#[cfg(feature = "tmr2")]
+pub mod tmr2 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA0<1>,
+
+            PA5<1>,
+
+            PA15<1>,
+
+            PB8<1>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PA1<1>,
+
+            PB3<1>,
+
+            PB9<1>,
+        ],
+
+        <Ch3> default: PushPull for [
+            PA2<1>,
+
+            PB10<1>,
+        ],
+
+        <Ch4> default: PushPull for [
+            PA3<1>,
+
+            PB2<1>,
+
+            PB11<1>,
+        ],
+    }
+
+    pin! {
+        <Ext, Input> for [
+            PA0<1>,
+
+            PA5<1>,
+
+            PA15<1>,
+        ],
+    }
+
+    use crate::pac::TMR2 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+    impl TmrCPin<2> for TMR {
+        type Ch<Otype> = Ch3<Otype>;
+    }
+    impl TmrCPin<3> for TMR {
+        type Ch<Otype> = Ch4<Otype>;
+    }
+    impl TmrExt for TMR {
+        type Ext = Ext;
+    }
+}
+
+#[cfg(feature = "tmr3")]
+pub mod tmr3 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA6<2>,
+
+            PB4<2>,
+
+            PC6<2>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PA7<2>,
+
+            PB5<2>,
+
+            PC7<2>,
+        ],
+
+        <Ch3> default: PushPull for [
+            PB0<2>,
+
+            PC8<2>,
+        ],
+
+        <Ch4> default: PushPull for [
+            PB1<2>,
+
+            PC9<2>,
+        ],
+    }
+
+    pin! {
+        <Ext, Input> for [
+            PD2<2>,
+        ],
+    }
+
+    use crate::pac::TMR3 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+    impl TmrCPin<2> for TMR {
+        type Ch<Otype> = Ch3<Otype>;
+    }
+    impl TmrCPin<3> for TMR {
+        type Ch<Otype> = Ch4<Otype>;
+    }
+    impl TmrExt for TMR {
+        type Ext = Ext;
+    }
+}
+
+#[cfg(feature = "tmr4")]
+pub mod tmr4 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PB6<2>,
+
+            PD12<2>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PB7<2>,
+
+            PD13<2>,
+        ],
+
+        <Ch3> default: PushPull for [
+            PB8<2>,
+
+            PD14<2>,
+        ],
+
+        <Ch4> default: PushPull for [
+            PB9<2>,
+
+            PD15<2>,
+        ],
+    }
+
+    pin! {
+        <Ext, Input> for [
+            PE0<2>,
+        ],
+    }
+
+    use crate::pac::TMR4 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+    impl TmrCPin<2> for TMR {
+        type Ch<Otype> = Ch3<Otype>;
+    }
+    impl TmrCPin<3> for TMR {
+        type Ch<Otype> = Ch4<Otype>;
+    }
+    impl TmrExt for TMR {
+        type Ext = Ext;
+    }
+}
+
+#[cfg(feature = "tmr5")]
+pub mod tmr5 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA0<2>,
+
+            PH10<2>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PA1<2>,
+
+            PH11<2>,
+        ],
+
+        <Ch3> default: PushPull for [
+            PA2<2>,
+
+            PH12<2>,
+        ],
+
+        <Ch4> default: PushPull for [
+            PA3<2>,
+        ],
+    }
+
+    use crate::pac::TMR5 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+    impl TmrCPin<2> for TMR {
+        type Ch<Otype> = Ch3<Otype>;
+    }
+    impl TmrCPin<3> for TMR {
+        type Ch<Otype> = Ch4<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr8")]
+pub mod tmr8 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PC6<3>,
+        ],
+
+        <Ch1N> default: PushPull for [
+            PA5<3>,
+
+            PA7<3>,
+
+            PH13<3>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PC7<3>,
+        ],
+
+        <Ch2N> default: PushPull for [
+            PB0<3>,
+
+            PB14<3>,
+
+            PH14<3>,
+        ],
+
+        <Ch3> default: PushPull for [
+            PC8<3>,
+        ],
+
+        <Ch3N> default: PushPull for [
+            PB1<3>,
+
+            PB15<3>,
+
+            PH15<3>,
+        ],
+
+        <Ch4> default: PushPull for [
+            PC9<3>,
+        ],
+    }
+
+    pin! {
+        <Bkin, Input> for [
+            PA6<3>,
+        ],
+
+        <Ext, Input> for [
+            PA0<3>,
+        ],
+    }
+
+    use crate::pac::TMR8 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+    impl TmrCPin<2> for TMR {
+        type Ch<Otype> = Ch3<Otype>;
+    }
+    impl TmrCPin<3> for TMR {
+        type Ch<Otype> = Ch4<Otype>;
+    }
+    impl TmrNCPin<0> for TMR {
+        type ChN<Otype> = Ch1N<Otype>;
+    }
+    impl TmrNCPin<1> for TMR {
+        type ChN<Otype> = Ch2N<Otype>;
+    }
+    impl TmrNCPin<2> for TMR {
+        type ChN<Otype> = Ch3N<Otype>;
+    }
+    impl TmrBkin for TMR {
+        type Bkin = Bkin;
+    }
+    impl TmrExt for TMR {
+        type Ext = Ext;
+    }
+}
+
+#[cfg(feature = "tmr9")]
+pub mod tmr9 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA2<3>,
+
+            PE5<3>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PA3<3>,
+
+            PE6<3>,
+        ],
+    }
+
+    use crate::pac::TMR9 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr10")]
+pub mod tmr10 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PB8<3>,
+
+            PF6<3>,
+        ],
+    }
+
+    use crate::pac::TMR10 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr11")]
+pub mod tmr11 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PB9<3>,
+
+            PF7<3>,
+        ],
+    }
+
+    use crate::pac::TMR11 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr12")]
+pub mod tmr12 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PB14<9>,
+
+            PH6<9>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PB15<9>,
+
+            PH9<9>,
+        ],
+    }
+
+    use crate::pac::TMR12 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr13")]
+pub mod tmr13 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA6<9>,
+
+            PF8<9>,
+        ],
+    }
+
+    use crate::pac::TMR13 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr14")]
+pub mod tmr14 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PA7<9>,
+
+            PF9<9>,
+        ],
+    }
+
+    use crate::pac::TMR14 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+}
+
+#[cfg(feature = "tmr20")]
+pub mod tmr20 {
+    use super::*;
+
+    pin! {
+        <Ch1> default: PushPull for [
+            PE2<6>,
+
+            PF12<6>,
+        ],
+
+        <Ch1N> default: PushPull for [
+            PE4<6>,
+
+            PF4<6>,
+        ],
+
+        <Ch2> default: PushPull for [
+            PE3<6>,
+
+            PF13<6>,
+        ],
+
+        <Ch2N> default: PushPull for [
+            PE5<6>,
+
+            PF5<6>,
+        ],
+
+        <Ch3> default: PushPull for [
+            PF2<6>,
+
+            PF14<6>,
+        ],
+
+        <Ch3N> default: PushPull for [
+            PE6<6>,
+
+            PF6<6>,
+        ],
+
+        <Ch4> default: PushPull for [
+            PE1<6>,
+
+            PF15<6>,
+        ],
+    }
+
+    pin! {
+        <Bkin, Input> for [
+            PF7<6>,
+
+            PG3<6>,
+        ],
+
+        <Ext, Input> for [
+            PE0<6>,
+
+            PG5<6>,
+        ],
+    }
+
+    use crate::pac::TMR20 as TMR;
+
+    impl TmrCPin<0> for TMR {
+        type Ch<Otype> = Ch1<Otype>;
+    }
+    impl TmrCPin<1> for TMR {
+        type Ch<Otype> = Ch2<Otype>;
+    }
+    impl TmrCPin<2> for TMR {
+        type Ch<Otype> = Ch3<Otype>;
+    }
+    impl TmrCPin<3> for TMR {
+        type Ch<Otype> = Ch4<Otype>;
+    }
+    impl TmrNCPin<0> for TMR {
+        type ChN<Otype> = Ch1N<Otype>;
+    }
+    impl TmrNCPin<1> for TMR {
+        type ChN<Otype> = Ch2N<Otype>;
+    }
+    impl TmrNCPin<2> for TMR {
+        type ChN<Otype> = Ch3N<Otype>;
+    }
+    impl TmrBkin for TMR {
+        type Bkin = Bkin;
+    }
+    impl TmrExt for TMR {
+        type Ext = Ext;
+    }
+}
+
+pub mod usart1 {
+    use super::*;
+
+    pin! {
+        <Ck, PushPull> for [
+            PA8<7>,
+        ],
+
+        <Cts, PushPull> for [
+            PA11<7>,
+        ],
+
+        <Rts, PushPull> for [
+            PA12<7>,
+        ],
+    }
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PA10<7>,
+
+            PB7<7>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PA9<7>,
+
+            PB6<7>,
+        ],
+    }
+
+    use crate::pac::USART1 as USART;
+
+    impl SerialAsync for USART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+    impl SerialSync for USART {
+        type Ck = Ck;
+    }
+    impl SerialRs232 for USART {
+        type Cts = Cts;
+        type Rts = Rts;
+    }
+}
+
+pub mod usart2 {
+    use super::*;
+
+    pin! {
+        <Ck, PushPull> for [
+            PA4<7>,
+
+            PD7<7>,
+        ],
+
+        <Cts, PushPull> for [
+            PA0<7>,
+
+            PD3<7>,
+        ],
+
+        <Rts, PushPull> for [
+            PA1<7>,
+
+            PD4<7>,
+        ],
+    }
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PA3<7>,
+
+            PD6<7>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PA2<7>,
+
+            PD5<7>,
+        ],
+    }
+
+    use crate::pac::USART2 as USART;
+
+    impl SerialAsync for USART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+    impl SerialSync for USART {
+        type Ck = Ck;
+    }
+    impl SerialRs232 for USART {
+        type Cts = Cts;
+        type Rts = Rts;
+    }
+}
+
+#[cfg(feature = "usart3")]
+pub mod usart3 {
+    use super::*;
+
+    pin! {
+        <Ck, PushPull> for [
+            PB12<7>,
+
+            PC12<7>,
+
+            PD10<7>,
+        ],
+
+        <Cts, PushPull> for [
+            PB13<7>,
+
+            PD11<7>,
+        ],
+
+        <Rts, PushPull> for [
+            PB14<7>,
+
+            PD12<7>,
+        ],
+    }
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PB11<7>,
+
+            PC11<7>,
+
+            PD9<7>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PB10<7>,
+
+            PC10<7>,
+
+            PD8<7>,
+        ],
+    }
+
+    use crate::pac::USART3 as USART;
+
+    impl SerialAsync for USART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+    impl SerialSync for USART {
+        type Ck = Ck;
+    }
+    impl SerialRs232 for USART {
+        type Cts = Cts;
+        type Rts = Rts;
+    }
+}
+
+#[cfg(feature = "usart6")]
+pub mod usart6 {
+    use super::*;
+
+    pin! {
+        <Ck, PushPull> for [
+            PC8<8>,
+
+            PG7<8>,
+        ],
+
+        <Cts, PushPull> for [
+            PG13<8>,
+
+            PG15<8>,
+        ],
+
+        <Rts, PushPull> for [
+            PG8<8>,
+
+            PG12<8>,
+        ],
+    }
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PC7<8>,
+
+            PG9<8>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PC6<8>,
+
+            PG14<8>,
+        ],
+    }
+
+    use crate::pac::USART6 as USART;
+
+    impl SerialAsync for USART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+    impl SerialSync for USART {
+        type Ck = Ck;
+    }
+    impl SerialRs232 for USART {
+        type Cts = Cts;
+        type Rts = Rts;
+    }
+}
+
+#[cfg(feature = "uart4")]
+pub mod uart4 {
+    use super::*;
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PA1<8>,
+
+            PC11<8>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PA0<8>,
+
+            PC10<8>,
+        ],
+    }
+
+    use crate::pac::UART4 as UART;
+
+    impl SerialAsync for UART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+}
+
+#[cfg(feature = "uart5")]
+pub mod uart5 {
+    use super::*;
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PB5<8>,
+
+            PB8<8>,
+
+            PB12<8>,
+
+            PD2<8>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PB6<8>,
+
+            PB9<8>,
+
+            PB13<8>,
+
+            PC12<8>,
+        ],
+    }
+
+    use crate::pac::UART5 as UART;
+
+    impl SerialAsync for UART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+}
+
+#[cfg(feature = "uart7")]
+pub mod uart7 {
+    use super::*;
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PB3<8>,
+
+            PE7<8>,
+
+            PF6<8>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PA15<8>,
+
+            PB4<8>,
+
+            PE8<8>,
+
+            PF7<8>,
+        ],
+    }
+
+    use crate::pac::UART7 as UART;
+
+    impl SerialAsync for UART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+}
+
+#[cfg(feature = "uart8")]
+pub mod uart8 {
+    use super::*;
+
+    pin! {
+        <Rx> default: PushPull for no:NoPin, [
+            PE0<8>,
+        ],
+
+        <Tx> default: PushPull for no:NoPin, [
+            PE1<8>,
+        ],
+    }
+
+    use crate::pac::UART8 as UART;
+
+    impl SerialAsync for UART {
+        type Rx<Otype> = Rx<Otype>;
+        type Tx<Otype> = Tx<Otype>;
+    }
+}
+
+pub mod spi1 {
+    use super::*;
+
+    pin! {
+        <Miso, PushPull> for no:NoPin, [
+            PA6<5>,
+
+            PB4<5>,
+        ],
+
+        <Mosi, PushPull> for no:NoPin, [
+            PA7<5>,
+
+            PB5<5>,
+        ],
+
+        <Nss, PushPull> for [
+            PA4<5>,
+
+            PA15<5>,
+        ],
+
+        <Sck, PushPull> for no:NoPin, [
+            PA5<5>,
+
+            PB3<5>,
+        ],
+    }
+
+    use crate::pac::SPI1 as SPI;
+
+    impl SpiCommon for SPI {
+        type Miso = Miso;
+        type Mosi = Mosi;
+        type Nss = Nss;
+        type Sck = Sck;
+    }
+}
+
+pub mod spi2 {
+    use super::*;
+
+    pin! {
+        <Miso, PushPull> for no:NoPin, [
+            PA12<5>,
+
+            PB14<5>,
+
+            PC2<5>,
+        ],
+
+        <Mosi, PushPull> for no:NoPin, [
+            PA10<5>,
+
+            PB15<5>,
+
+            PC3<5>,
+        ],
+
+        <Nss, PushPull> for [
+            PA11<5>,
+
+            PB9<5>,
+
+            PB12<5>,
+        ],
+
+        <Sck, PushPull> for no:NoPin, [
+            PA9<5>,
+
+            PB10<5>,
+
+            PB13<5>,
+
+            PD3<5>,
+        ],
+    }
+
+    use crate::pac::SPI2 as SPI;
+
+    impl SpiCommon for SPI {
+        type Miso = Miso;
+        type Mosi = Mosi;
+        type Nss = Nss;
+        type Sck = Sck;
+    }
+}
+
+pub mod spi3 {
+    use super::*;
+
+    pin! {
+        <Miso, PushPull> for no:NoPin, [
+            PB4<6>,
+
+            PC11<6>,
+        ],
+
+        <Mosi, PushPull> for no:NoPin, [
+            PB5<6>,
+
+            PC12<6>,
+
+            PD6<5>,
+        ],
+
+        <Nss, PushPull> for [
+            PA4<6>,
+
+            PA15<6>,
+        ],
+
+        <Sck, PushPull> for no:NoPin, [
+            PB3<6>,
+
+            PC10<6>,
+        ],
+    }
+
+    use crate::pac::SPI3 as SPI;
+
+    impl SpiCommon for SPI {
+        type Miso = Miso;
+        type Mosi = Mosi;
+        type Nss = Nss;
+        type Sck = Sck;
+    }
+}
+
+pub mod spi4 {
+    use super::*;
+
+    pin! {
+        <Miso, PushPull> for no:NoPin, [
+            PE5<5>,
+
+            PE13<5>,
+        ],
+
+        <Mosi, PushPull> for no:NoPin, [
+            PA1<5>,
+
+            PE6<5>,
+
+            PE14<5>,
+        ],
+
+        <Nss, PushPull> for [
+            PE4<5>,
+
+            PE11<5>,
+        ],
+
+        <Sck, PushPull> for no:NoPin, [
+            PE2<5>,
+
+            PE12<5>,
+        ],
+    }
+
+    use crate::pac::SPI4 as SPI;
+
+    impl SpiCommon for SPI {
+        type Miso = Miso;
+        type Mosi = Mosi;
+        type Nss = Nss;
+        type Sck = Sck;
+    }
+}
+
+pub mod i2c1 {
+    use super::*;
+
+    pin! {
+        <Scl, OpenDrain> for [
+            PB6<4>,
+
+            PB8<4>,
+        ],
+
+        <Sda, OpenDrain> for [
+            PB7<4>,
+
+            PB9<4>,
+        ],
+
+        <Smba, OpenDrain> for [
+            PB5<4>,
+        ],
+    }
+
+    use crate::pac::I2C1 as I2C;
+
+    impl I2cCommon for I2C {
+        type Scl = Scl;
+        type Sda = Sda;
+        type Smba = Smba;
+    }
+}
+
+pub mod i2c2 {
+    use super::*;
+
+    pin! {
+        <Scl, OpenDrain> for [
+            PB10<4>,
+
+            PF1<4>,
+
+            PH4<4>,
+        ],
+
+        <Sda, OpenDrain> for [
+            PB11<4>,
+
+            PF0<4>,
+
+            PH5<4>,
+        ],
+
+        <Smba, OpenDrain> for [
+            PB12<4>,
+
+            PF2<4>,
+
+            PH6<4>,
+        ],
+    }
+
+    use crate::pac::I2C2 as I2C;
+
+    impl I2cCommon for I2C {
+        type Scl = Scl;
+        type Sda = Sda;
+        type Smba = Smba;
+    }
+}
+
+#[cfg(feature = "i2c3")]
+pub mod i2c3 {
+    use super::*;
+
+    pin! {
+        <Scl, OpenDrain> for [
+            PA8<4>,
+
+            PH7<4>,
+        ],
+
+        <Sda, OpenDrain> for [
+            PC9<4>,
+
+            PH8<4>,
+        ],
+
+        <Smba, OpenDrain> for [
+            PA9<4>,
+
+            PH9<4>,
+        ],
+    }
+
+    use crate::pac::I2C3 as I2C;
+
+    impl I2cCommon for I2C {
+        type Scl = Scl;
+        type Sda = Sda;
+        type Smba = Smba;
+    }
+}
+
+#[cfg(feature = "can1")]
+pub mod can1 {
+    use super::*;
+
+    pin! {
+        <Rx, PushPull> for [
+            PA11<9>,
+
+            PB8<9>,
+
+            PD0<9>,
+
+            PH14<9>,
+        ],
+
+        <Tx, PushPull> for [
+            PA12<9>,
+
+            PB9<9>,
+
+            PD1<9>,
+
+            PH13<9>,
+        ],
+    }
+
+    use crate::pac::CAN1 as CAN;
+
+    impl CanCommon for CAN {
+        type Rx = Rx;
+        type Tx = Tx;
+    }
+}
+
+#[cfg(feature = "can2")]
+pub mod can2 {
+    use super::*;
+
+    pin! {
+        <Rx, PushPull> for [
+            PB5<9>,
+
+            PB12<9>,
+        ],
+
+        <Tx, PushPull> for [
+            PB6<9>,
+
+            PB13<9>,
+        ],
+    }
+
+    use crate::pac::CAN2 as CAN;
+
+    impl CanCommon for CAN {
+        type Rx = Rx;
+        type Tx = Tx;
+    }
+}
+
+pub mod sdio1 {
+    use super::*;
+
+    pin! {
+        <Ck, PushPull> for [
+            PC12<12>,
+        ],
+
+        <Cmd, PushPull> for [
+            PD2<12>,
+        ],
+
+        <D0, PushPull> for [
+            PC8<12>,
+        ],
+
+        <D1, PushPull> for [
+            PC9<12>,
+        ],
+
+        <D2, PushPull> for [
+            PC10<12>,
+        ],
+
+        <D3, PushPull> for [
+            PC11<12>,
+        ],
+
+        <D4, PushPull> for [
+            PB8<12>,
+        ],
+
+        <D5, PushPull> for [
+            PB9<12>,
+        ],
+
+        <D6, PushPull> for [
+            PC6<12>,
+        ],
+
+        <D7, PushPull> for [
+            PC7<12>,
+        ],
+    }
+}
+
+pub mod qspi1 {
+    use super::*;
+
+    pin! {
+        <Sck, PushPull> for [
+            PB1<9>,
+
+            PB2<9>,
+
+            PF10<9>,
+        ],
+
+        <Cs, PushPull> for [
+            PB6<10>,
+
+            PB10<9>,
+
+            PG6<10>,
+        ],
+
+        <Io0, PushPull> for [
+            PF8<10>,
+
+            PD11<9>,
+        ],
+
+        <Io1, PushPull> for [
+            PF9<10>,
+
+            PD12<9>,
+        ],
+
+        <Io2, PushPull> for [
+            PF7<9>,
+
+            PE2<9>,
+        ],
+
+        <Io3, PushPull> for [
+            PF6<9>,
+
+            PA1<9>,
+
+            PD13<9>,
+        ],
+    }
+}
+
+pub mod xmc {
+    use super::*;
+
+    pin! {
+        <A0, PushPull> for [
+            PF0<12>,
+        ],
+
+        <A1, PushPull> for [
+            PF1<12>,
+        ],
+
+        <A2, PushPull> for [
+            PF2<12>,
+        ],
+
+        <A3, PushPull> for [
+            PF3<12>,
+        ],
+
+        <A4, PushPull> for [
+            PF4<12>,
+        ],
+
+        <A5, PushPull> for [
+            PF5<12>,
+        ],
+
+        <A6, PushPull> for [
+            PF12<12>,
+        ],
+
+        <A7, PushPull> for [
+            PF13<12>,
+        ],
+
+        <A8, PushPull> for [
+            PF14<12>,
+        ],
+
+        <A9, PushPull> for [
+            PF15<12>,
+        ],
+
+        <A10, PushPull> for [
+            PG0<12>,
+        ],
+
+        <A11, PushPull> for [
+            PG1<12>,
+        ],
+
+        <A12, PushPull> for [
+            PG2<12>,
+        ],
+
+        <A13, PushPull> for [
+            PG3<12>,
+        ],
+
+        <A14, PushPull> for [
+            PG4<12>,
+        ],
+
+        <A15, PushPull> for [
+            PG5<12>,
+        ],
+
+        <A16, PushPull> for [
+            PD11<12>,
+        ],
+
+        <A17, PushPull> for [
+            PD12<12>,
+        ],
+
+        <A18, PushPull> for [
+            PD13<12>,
+        ],
+
+        <A19, PushPull> for [
+            PE3<12>,
+        ],
+
+        <A20, PushPull> for [
+            PE4<12>,
+        ],
+
+        <A21, PushPull> for [
+            PE5<12>,
+        ],
+
+        <A22, PushPull> for [
+            PE6<12>,
+        ],
+
+        <A23, PushPull> for [
+            PE2<12>,
+        ],
+
+        <D0, PushPull> for [
+            PD14<12>,
+        ],
+
+        <D1, PushPull> for [
+            PD15<12>,
+        ],
+
+        <D2, PushPull> for [
+            PD0<12>,
+        ],
+
+        <D3, PushPull> for [
+            PD1<12>,
+        ],
+
+        <D4, PushPull> for [
+            PE7<12>,
+        ],
+
+        <D5, PushPull> for [
+            PE8<12>,
+        ],
+
+        <D6, PushPull> for [
+            PE9<12>,
+        ],
+
+        <D7, PushPull> for [
+            PE10<12>,
+        ],
+
+        <D8, PushPull> for [
+            PE11<12>,
+        ],
+
+        <D9, PushPull> for [
+            PE12<12>,
+        ],
+
+        <D10, PushPull> for [
+            PE13<12>,
+        ],
+
+        <D11, PushPull> for [
+            PE14<12>,
+        ],
+
+        <D12, PushPull> for [
+            PE15<12>,
+        ],
+
+        <D13, PushPull> for [
+            PD8<12>,
+        ],
+
+        <D14, PushPull> for [
+            PD9<12>,
+        ],
+
+        <D15, PushPull> for [
+            PD10<12>,
+        ],
+
+        <Clk, PushPull> for [
+            PD3<12>,
+        ],
+
+        <Nadv, PushPull> for [
+            PB7<12>,
+        ],
+
+        <Nbl0, PushPull> for [
+            PE0<12>,
+        ],
+
+        <Nbl1, PushPull> for [
+            PE1<12>,
+        ],
+
+        <Ne1, PushPull> for [
+            PD7<12>,
+        ],
+
+        <Ne2, PushPull> for [
+            PG9<12>,
+        ],
+
+        <Ne3, PushPull> for [
+            PG10<12>,
+        ],
+
+        <Ne4, PushPull> for [
+            PG12<12>,
+        ],
+
+        <Noe, PushPull> for [
+            PD4<12>,
+        ],
+
+        <Nwait, PushPull> for [
+            PD6<12>,
+        ],
+
+        <Nwe, PushPull> for [
+            PD5<12>,
+        ],
+    }
+}
+
+#[cfg(feature = "emac")]
+pub mod emac {
+    use super::*;
+
+    pin! {
+        <Mdc, PushPull> for [
+            PC1<11>,
+        ],
+
+        <Mdio, PushPull> for [
+            PA2<11>,
+        ],
+
+        <MiiCol, PushPull> for [
+            PA3<11>,
+
+            PH3<11>,
+        ],
+
+        <MiiCrs, PushPull> for [
+            PA0<11>,
+
+            PH2<11>,
+        ],
+
+        <MiiRxClk, PushPull> for [
+            PA1<11>,
+        ],
+
+        <MiiRxd2, PushPull> for [
+            PB0<11>,
+
+            PH6<11>,
+        ],
+
+        <MiiRxd3, PushPull> for [
+            PB1<11>,
+
+            PH7<11>,
+        ],
+
+        <MiiRxEr, PushPull> for [
+            PB10<11>,
+        ],
+
+        <MiiTxClk, PushPull> for [
+            PC3<11>,
+        ],
+
+        <MiiTxd2, PushPull> for [
+            PC2<11>,
+        ],
+
+        <MiiTxd3, PushPull> for [
+            PB8<11>,
+
+            PE2<11>,
+        ],
+
+        <PpsOut, PushPull> for [
+            PB5<11>,
+
+            PG8<11>,
+        ],
+
+        <RmiiCrsDv, PushPull> for [
+            PA7<11>,
+        ],
+
+        <RmiiRefClk, PushPull> for [
+            PA1<11>,
+        ],
+
+        <Rxd0, PushPull> for [
+            PC4<11>,
+        ],
+
+        <Rxd1, PushPull> for [
+            PC5<11>,
+        ],
+
+        <TxEn, PushPull> for [
+            PB11<11>,
+
+            PG11<11>,
+        ],
+
+        <Txd0, PushPull> for [
+            PB12<11>,
+
+            PG13<11>,
+        ],
+
+        <Txd1, PushPull> for [
+            PB13<11>,
+
+            PG14<11>,
+        ],
+    }
+}
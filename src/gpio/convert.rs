@@ -433,3 +433,9 @@ impl<const A: u8> PinMode for Alternate<A, Input> {
     const IOMC: u32 = 0b00;
     const IOFC: u32 = 0b01;
 }
+
+#[cfg(feature = "new-gpio")]
+impl<const A: u8> PinMode for Alternate<A, Input> {
+    const CFGR: u32 = 0b10;
+    const MUX: Option<u32> = Some(A as _);
+}
@@ -21,6 +21,12 @@ pub enum Dynamic {
     OutputPushPull,
     /// Open-drain output mode
     OutputOpenDrain,
+    /// Analog mode
+    Analog,
+    /// Push-pull alternate function mode
+    Alternate(u8),
+    /// Open-drain alternate function mode
+    AlternateOpenDrain(u8),
 }
 
 /// Error for [DynamicPin]
@@ -36,7 +42,8 @@ impl Dynamic {
         use Dynamic::*;
         match self {
             InputFloating | InputPullUp | InputPullDown | OutputOpenDrain => true,
-            OutputPushPull => false,
+            Alternate(_) | AlternateOpenDrain(_) => true,
+            OutputPushPull | Analog => false,
         }
     }
 
@@ -44,10 +51,16 @@ impl Dynamic {
     pub fn is_output(&self) -> bool {
         use Dynamic::*;
         match self {
-            InputFloating | InputPullUp | InputPullDown => false,
+            InputFloating | InputPullUp | InputPullDown | Analog => false,
+            Alternate(_) | AlternateOpenDrain(_) => false,
             OutputPushPull | OutputOpenDrain => true,
         }
     }
+
+    /// Is pin in alternate function mode
+    pub fn is_alternate(&self) -> bool {
+        matches!(self, Dynamic::Alternate(_) | Dynamic::AlternateOpenDrain(_))
+    }
 }
 
 // For conversion simplify
@@ -55,6 +68,8 @@ struct Unknown;
 
 impl crate::Sealed for Unknown {}
 impl PinMode for Unknown {}
+impl marker::Active for Unknown {}
+impl marker::OutputSpeed for Unknown {}
 
 impl<const P: char, const N: u8> DynamicPin<P, N> {
     pub(super) const fn new(mode: Dynamic) -> Self {
@@ -111,6 +126,102 @@ impl<const P: char, const N: u8> DynamicPin<P, N> {
         self.mode = Dynamic::OutputOpenDrain;
     }
 
+    /// Switch pin into analog mode
+    #[inline]
+    pub fn make_analog(&mut self) {
+        // NOTE(unsafe), we have a mutable reference to the current pin
+        Pin::<P, N, Unknown>::new().into_analog();
+        self.mode = Dynamic::Analog;
+    }
+    /// Switch pin into push-pull alternate function `af`
+    ///
+    /// On legacy-gpio parts `af` is only recorded, the routing is selected with [`Remap`](crate::iomux::Remap).
+    #[inline]
+    pub fn make_alternate(&mut self, af: u8) {
+        self.set_alternate(af, false);
+        self.mode = Dynamic::Alternate(af);
+    }
+    /// Switch pin into open-drain alternate function `af`
+    ///
+    /// On legacy-gpio parts `af` is only recorded, the routing is selected with [`Remap`](crate::iomux::Remap).
+    #[inline]
+    pub fn make_alternate_open_drain(&mut self, af: u8) {
+        self.set_alternate(af, true);
+        self.mode = Dynamic::AlternateOpenDrain(af);
+    }
+
+    #[cfg(feature = "legacy-gpio")]
+    fn set_alternate(&mut self, _af: u8, open_drain: bool) {
+        let mut pin = Pin::<P, N, Unknown>::new();
+        if open_drain {
+            pin.mode::<super::Alternate<0, OpenDrain>>();
+        } else {
+            pin.mode::<super::Alternate<0, PushPull>>();
+        }
+    }
+
+    #[cfg(feature = "new-gpio")]
+    fn set_alternate(&mut self, af: u8, open_drain: bool) {
+        assert!(af < 16);
+        let gpio = unsafe { &*Gpio::<P>::ptr() };
+        let af = af as u32;
+        unsafe {
+            gpio.omode()
+                .modify(|r, w| w.bits(r.bits() & !(0b1 << N) | ((open_drain as u32) << N)));
+            if N < 8 {
+                let offset = 4 * { N };
+                gpio.muxl()
+                    .modify(|r, w| w.bits((r.bits() & !(0b1111 << offset)) | (af << offset)));
+            } else {
+                let offset = 4 * { N - 8 };
+                gpio.muxh()
+                    .modify(|r, w| w.bits((r.bits() & !(0b1111 << offset)) | (af << offset)));
+            }
+            let offset = 2 * { N };
+            gpio.cfgr()
+                .modify(|r, w| w.bits((r.bits() & !(0b11 << offset)) | (0b10 << offset)));
+        }
+    }
+
+    /// Set pin speed
+    ///
+    /// Only available in output and alternate function modes.
+    pub fn set_speed(&mut self, speed: Speed) -> Result<(), PinModeError> {
+        if self.mode.is_output() || self.mode.is_alternate() {
+            Pin::<P, N, Unknown>::new().set_speed(speed);
+            Ok(())
+        } else {
+            Err(PinModeError::IncorrectMode)
+        }
+    }
+
+    /// Set the internal pull-up and pull-down resistor
+    ///
+    /// Input modes are switched to the matching pull variant.
+    /// On new-gpio parts the resistor can also be set in output and alternate function modes.
+    pub fn set_internal_resistor(&mut self, resistor: Pull) -> Result<(), PinModeError> {
+        use Dynamic::*;
+        match self.mode {
+            InputFloating | InputPullUp | InputPullDown => {
+                self.mode = match resistor {
+                    Pull::None => InputFloating,
+                    Pull::Up => InputPullUp,
+                    Pull::Down => InputPullDown,
+                };
+            }
+            #[cfg(feature = "new-gpio")]
+            OutputPushPull | OutputOpenDrain | Alternate(_) | AlternateOpenDrain(_) => {}
+            _ => return Err(PinModeError::IncorrectMode),
+        }
+        Pin::<P, N, Unknown>::new().set_internal_resistor(resistor);
+        Ok(())
+    }
+
+    /// Current pin mode
+    pub fn mode(&self) -> &Dynamic {
+        &self.mode
+    }
+
     /// Drives the pin high
     pub fn set_high(&mut self) -> Result<(), PinModeError> {
         if self.mode.is_output() {
@@ -144,4 +255,57 @@ impl<const P: char, const N: u8> DynamicPin<P, N> {
             Err(PinModeError::IncorrectMode)
         }
     }
+
+    /// Is the pin in drive high mode?
+    pub fn is_set_high(&self) -> Result<bool, PinModeError> {
+        self.is_set_low().map(|b| !b)
+    }
+
+    /// Is the pin in drive low mode?
+    pub fn is_set_low(&self) -> Result<bool, PinModeError> {
+        if self.mode.is_output() {
+            Ok(Pin::<P, N, Unknown>::new()._is_set_low())
+        } else {
+            Err(PinModeError::IncorrectMode)
+        }
+    }
+}
+
+impl embedded_hal::digital::Error for PinModeError {
+    fn kind(&self) -> embedded_hal::digital::ErrorKind {
+        match self {
+            PinModeError::IncorrectMode => embedded_hal::digital::ErrorKind::Other,
+        }
+    }
+}
+
+impl<const P: char, const N: u8> ErrorType for DynamicPin<P, N> {
+    type Error = PinModeError;
+}
+
+impl<const P: char, const N: u8> OutputPin for DynamicPin<P, N> {
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.set_high()
+    }
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.set_low()
+    }
+}
+
+impl<const P: char, const N: u8> StatefulOutputPin for DynamicPin<P, N> {
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        DynamicPin::is_set_high(self)
+    }
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        DynamicPin::is_set_low(self)
+    }
+}
+
+impl<const P: char, const N: u8> InputPin for DynamicPin<P, N> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        DynamicPin::is_high(self)
+    }
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        DynamicPin::is_low(self)
+    }
 }
@@ -154,6 +154,10 @@ impl<MODE> ErasedPin<Output<MODE>> {
     }
 }
 
+impl<MODE> ErrorType for ErasedPin<MODE> {
+    type Error = Infallible;
+}
+
 impl<MODE> ErasedPin<MODE>
 where
     MODE: marker::Readable,
@@ -0,0 +1,306 @@
+use super::{marker, Edge, PinExt};
+use crate::pac::EXINT;
+
+#[cfg(feature = "legacy-gpio")]
+pub use crate::iomux::Iomux as ExintMux;
+#[cfg(feature = "new-gpio")]
+pub use crate::scfg::Scfg as ExintMux;
+
+/// External Interrupt Pin
+pub trait ExtiPin {
+    /// Make corresponding EXINT line sensitive to this pin
+    fn make_interrupt_source(&mut self, mux: &mut ExintMux);
+
+    /// Generate interrupt on rising edge, falling edge or both
+    fn trigger_on_edge(&mut self, exint: &mut EXINT, level: Edge);
+
+    /// Enable external interrupts from this pin.
+    fn enable_interrupt(&mut self, exint: &mut EXINT);
+
+    /// Disable external interrupts from this pin
+    fn disable_interrupt(&mut self, exint: &mut EXINT);
+
+    /// Enable event generation from this pin
+    fn enable_event(&mut self, exint: &mut EXINT);
+
+    /// Disable event generation from this pin
+    fn disable_event(&mut self, exint: &mut EXINT);
+
+    /// Clear the interrupt pending bit for this pin
+    fn clear_interrupt_pending_bit(&mut self);
+
+    /// Reads the interrupt pending bit for this pin
+    fn check_interrupt(&self) -> bool;
+}
+
+impl<PIN> ExtiPin for PIN
+where
+    PIN: PinExt,
+    PIN::Mode: marker::Interruptible,
+{
+    #[inline(always)]
+    fn make_interrupt_source(&mut self, mux: &mut ExintMux) {
+        mux.set_exint_source(self.pin_id(), self.port_id());
+    }
+
+    #[inline(always)]
+    fn trigger_on_edge(&mut self, exint: &mut EXINT, edge: Edge) {
+        set_edge(exint, self.pin_id(), edge);
+    }
+
+    #[inline(always)]
+    fn enable_interrupt(&mut self, exint: &mut EXINT) {
+        let i = self.pin_id();
+        exint
+            .inten()
+            .modify(|r, w| unsafe { w.bits(r.bits() | (1 << i)) });
+    }
+
+    #[inline(always)]
+    fn disable_interrupt(&mut self, exint: &mut EXINT) {
+        let i = self.pin_id();
+        exint
+            .inten()
+            .modify(|r, w| unsafe { w.bits(r.bits() & !(1 << i)) });
+    }
+
+    #[inline(always)]
+    fn enable_event(&mut self, exint: &mut EXINT) {
+        let i = self.pin_id();
+        exint
+            .evten()
+            .modify(|r, w| unsafe { w.bits(r.bits() | (1 << i)) });
+    }
+
+    #[inline(always)]
+    fn disable_event(&mut self, exint: &mut EXINT) {
+        let i = self.pin_id();
+        exint
+            .evten()
+            .modify(|r, w| unsafe { w.bits(r.bits() & !(1 << i)) });
+    }
+
+    #[inline(always)]
+    fn clear_interrupt_pending_bit(&mut self) {
+        // NOTE(unsafe) atomic write to a write-1-to-clear register
+        unsafe {
+            (*EXINT::ptr())
+                .intsts()
+                .write(|w| w.bits(1 << self.pin_id()))
+        };
+    }
+
+    #[inline(always)]
+    fn check_interrupt(&self) -> bool {
+        unsafe { ((*EXINT::ptr()).intsts().read().bits() & (1 << self.pin_id())) != 0 }
+    }
+}
+
+fn set_edge(exint: &EXINT, i: u8, edge: Edge) {
+    let (rising, falling) = match edge {
+        Edge::Rising => (true, false),
+        Edge::Falling => (false, true),
+        Edge::RisingFalling => (true, true),
+    };
+    exint
+        .polcfg1()
+        .modify(|r, w| unsafe { w.bits(r.bits() & !(1 << i) | ((rising as u32) << i)) });
+    exint
+        .polcfg2()
+        .modify(|r, w| unsafe { w.bits(r.bits() & !(1 << i) | ((falling as u32) << i)) });
+}
+
+#[cfg(feature = "async")]
+pub use asynch::on_interrupt;
+
+#[cfg(feature = "async")]
+mod asynch {
+    use core::future::Future;
+    use core::pin::Pin as FuturePin;
+    use core::task::{Context, Poll};
+
+    use embedded_hal_async::digital::Wait;
+
+    use super::super::{marker, ErasedPin, Pin, PinExt};
+    use super::{set_edge, Edge};
+    use crate::pac::EXINT;
+    use crate::waker::WakerCell;
+
+    static WAKERS: [WakerCell; 16] = [WakerCell::NEW; 16];
+
+    /// Shared EXINT interrupt dispatcher for [`Wait`], exported as `gpio::on_exint_interrupt`
+    ///
+    /// Call it from every EXINT interrupt handler that serves pins used with `Wait`.
+    /// Pending lines are masked and their tasks are woken, other lines are left untouched.
+    ///
+    /// `Wait` doesn't change the EXINT mux, the pin must be routed to its line with
+    /// [`ExtiPin::make_interrupt_source`](super::ExtiPin::make_interrupt_source) first.
+    /// Debug builds panic if the line is routed to the same pin of another port.
+    ///
+    /// ```rust
+    /// let mut scfg = dp.SCFG.constrain(); // `dp.IOMUX.constrain()` on legacy GPIO parts
+    /// let mut button = gpiob.pb0.into_pull_up_input();
+    /// button.make_interrupt_source(&mut scfg);
+    /// unsafe { NVIC::unmask(pac::Interrupt::EXINT0) };
+    ///
+    /// #[interrupt]
+    /// fn EXINT0() {
+    ///     gpio::on_exint_interrupt();
+    /// }
+    ///
+    /// // in an async task
+    /// button.wait_for_falling_edge().await.unwrap();
+    /// ```
+    pub fn on_interrupt() {
+        let exint = unsafe { &*EXINT::ptr() };
+        let pending = exint.intsts().read().bits() & exint.inten().read().bits() & 0xffff;
+        if pending == 0 {
+            return;
+        }
+        exint
+            .inten()
+            .modify(|r, w| unsafe { w.bits(r.bits() & !pending) });
+        // NOTE(unsafe) atomic write to a write-1-to-clear register
+        exint.intsts().write(|w| unsafe { w.bits(pending) });
+        for (i, waker) in WAKERS.iter().enumerate() {
+            if pending & (1 << i) != 0 {
+                waker.wake();
+            }
+        }
+    }
+
+    /// Waits for the next edge on EXINT line `line`
+    ///
+    /// The line is armed on creation, so levels can be checked afterwards without missing an edge.
+    struct ExintFuture {
+        line: u8,
+    }
+
+    impl ExintFuture {
+        fn new(line: u8, port: u8, edge: Edge) -> Self {
+            debug_assert_eq!(
+                exint_source(line),
+                port,
+                "EXINT line not routed to the pin, call make_interrupt_source"
+            );
+            cortex_m::interrupt::free(|_| {
+                let exint = unsafe { &*EXINT::ptr() };
+                set_edge(exint, line, edge);
+                exint.intsts().write(|w| unsafe { w.bits(1 << line) });
+                exint
+                    .inten()
+                    .modify(|r, w| unsafe { w.bits(r.bits() | (1 << line)) });
+            });
+            Self { line }
+        }
+    }
+
+    impl Future for ExintFuture {
+        type Output = ();
+
+        fn poll(self: FuturePin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            WAKERS[self.line as usize].register(cx.waker());
+            // The dispatcher masks the line once the edge has been seen
+            let exint = unsafe { &*EXINT::ptr() };
+            if exint.inten().read().bits() & (1 << self.line) == 0 {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    impl Drop for ExintFuture {
+        fn drop(&mut self) {
+            let line = self.line;
+            cortex_m::interrupt::free(|_| {
+                let exint = unsafe { &*EXINT::ptr() };
+                exint
+                    .inten()
+                    .modify(|r, w| unsafe { w.bits(r.bits() & !(1 << line)) });
+            });
+        }
+    }
+
+    /// Port routed to EXINT line `line` by the mux
+    fn exint_source(line: u8) -> u8 {
+        #[cfg(feature = "legacy-gpio")]
+        let mux = unsafe { &*crate::pac::IOMUX::ptr() };
+        #[cfg(feature = "new-gpio")]
+        let mux = unsafe { &*crate::pac::SCFG::ptr() };
+        let bits = match line {
+            0..=3 => mux.exintc1().read().bits(),
+            4..=7 => mux.exintc2().read().bits(),
+            8..=11 => mux.exintc3().read().bits(),
+            _ => mux.exintc4().read().bits(),
+        };
+        ((bits >> (4 * (line % 4))) & 0xf) as u8
+    }
+
+    async fn wait_for_level(line: u8, port: u8, high: bool, is_low: impl Fn() -> bool) {
+        let fut = ExintFuture::new(line, port, if high { Edge::Rising } else { Edge::Falling });
+        if is_low() == high {
+            fut.await
+        }
+    }
+
+    impl<const P: char, const N: u8, MODE> Wait for Pin<P, N, MODE>
+    where
+        MODE: marker::Interruptible + marker::Readable,
+    {
+        async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+            wait_for_level(N, self.port_id(), true, || self._is_low()).await;
+            Ok(())
+        }
+
+        async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+            wait_for_level(N, self.port_id(), false, || self._is_low()).await;
+            Ok(())
+        }
+
+        async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+            ExintFuture::new(N, self.port_id(), Edge::Rising).await;
+            Ok(())
+        }
+
+        async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+            ExintFuture::new(N, self.port_id(), Edge::Falling).await;
+            Ok(())
+        }
+
+        async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+            ExintFuture::new(N, self.port_id(), Edge::RisingFalling).await;
+            Ok(())
+        }
+    }
+
+    impl<MODE> Wait for ErasedPin<MODE>
+    where
+        MODE: marker::Interruptible + marker::Readable,
+    {
+        async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+            wait_for_level(self.pin_id(), self.port_id(), true, || self.is_low()).await;
+            Ok(())
+        }
+
+        async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+            wait_for_level(self.pin_id(), self.port_id(), false, || self.is_low()).await;
+            Ok(())
+        }
+
+        async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+            ExintFuture::new(self.pin_id(), self.port_id(), Edge::Rising).await;
+            Ok(())
+        }
+
+        async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+            ExintFuture::new(self.pin_id(), self.port_id(), Edge::Falling).await;
+            Ok(())
+        }
+
+        async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+            ExintFuture::new(self.pin_id(), self.port_id(), Edge::RisingFalling).await;
+            Ok(())
+        }
+    }
+}
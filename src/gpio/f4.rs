@@ -8,17 +8,43 @@ where
 {
     /// Set pin speed
     pub fn set_speed(&mut self, speed: Speed) {
+        self.set_drive_strength(speed.into())
+    }
+
+    /// Set pin speed
+    pub fn speed(mut self, speed: Speed) -> Self {
+        self.set_speed(speed);
+        self
+    }
+
+    /// Set output drive strength
+    pub fn set_drive_strength(&mut self, drive: DriveStrength) {
         let offset = 2 * { N };
         unsafe {
             (*Gpio::<P>::ptr())
                 .odrvr()
-                .modify(|r, w| w.bits((r.bits() & !(0b11 << offset)) | ((speed as u32) << offset)));
+                .modify(|r, w| w.bits((r.bits() & !(0b11 << offset)) | ((drive as u32) << offset)));
         }
     }
 
-    /// Set pin speed
-    pub fn speed(mut self, speed: Speed) -> Self {
-        self.set_speed(speed);
+    /// Set output drive strength
+    pub fn drive_strength(mut self, drive: DriveStrength) -> Self {
+        self.set_drive_strength(drive);
+        self
+    }
+
+    /// Enables / disables huge sink current mode
+    pub fn set_huge_sink(&mut self, on: bool) {
+        unsafe {
+            (*Gpio::<P>::ptr())
+                .hdrv()
+                .modify(|r, w| w.bits((r.bits() & !(0b1 << N)) | ((on as u32) << N)));
+        }
+    }
+
+    /// Enables / disables huge sink current mode
+    pub fn huge_sink(mut self, on: bool) -> Self {
+        self.set_huge_sink(on);
         self
     }
 }
@@ -110,3 +136,436 @@ gpio!(GPIOF, gpiof, PF, 'F', PFn, [
     PF6: (pf6, 6, [0]),
     PF7: (pf7, 7, [0]),
 ]);
+
+#[cfg(any(feature = "at32f402", feature = "at32f405"))]
+gpio!(GPIOA, gpioa, PA, 'A', PAn, [
+    PA0: (pa0, 0, [1, 7, 8]),
+    PA1: (pa1, 1, [1, 7, 8]),
+    PA2: (pa2, 2, [1, 3, 7]),
+    PA3: (pa3, 3, [1, 3, 7]),
+    PA4: (pa4, 4, [5, 6, 7]),
+    PA5: (pa5, 5, [1, 5]),
+    PA6: (pa6, 6, [1, 2, 5, 9]),
+    PA7: (pa7, 7, [1, 2, 5, 9]),
+    PA8: (pa8, 8, [0, 1, 4, 7, 10]),
+    PA9: (pa9, 9, [1, 4, 7, 10]),
+    PA10: (pa10, 10, [1, 7, 10]),
+    PA11: (pa11, 11, [1, 7, 9]),
+    PA12: (pa12, 12, [1, 7, 9]),
+    PA13: (pa13, 13, [0], super::Debugger), // SWDIO, PullUp VeryHigh speed
+    PA14: (pa14, 14, [0, 7], super::Debugger), // SWCLK, PullDown
+    PA15: (pa15, 15, [0, 1, 5, 6, 7]),
+]);
+
+#[cfg(any(feature = "at32f402", feature = "at32f405"))]
+gpio!(GPIOB, gpiob, PB, 'B', PBn, [
+    PB0: (pb0, 0, [1, 2]),
+    PB1: (pb1, 1, [1, 2]),
+    PB2: (pb2, 2, [1]),
+    PB3: (pb3, 3, [0, 1, 5, 6, 8]),
+    PB4: (pb4, 4, [0, 2, 5, 6, 8]),
+    PB5: (pb5, 5, [2, 4, 5, 6]),
+    PB6: (pb6, 6, [2, 4, 7]),
+    PB7: (pb7, 7, [2, 4, 7]),
+    PB8: (pb8, 8, [1, 2, 3, 4, 9]),
+    PB9: (pb9, 9, [1, 2, 3, 4, 5, 9]),
+    PB10: (pb10, 10, [1, 4, 5, 7]),
+    PB11: (pb11, 11, [1, 4, 7]),
+    PB12: (pb12, 12, [1, 4, 5, 7]),
+    PB13: (pb13, 13, [1, 5, 7]),
+    PB14: (pb14, 14, [1, 5, 7]),
+    PB15: (pb15, 15, [1, 5]),
+]);
+
+#[cfg(any(feature = "at32f402", feature = "at32f405"))]
+gpio!(GPIOC, gpioc, PC, 'C', PCn, [
+    PC0: (pc0, 0, [4, 8]),
+    PC1: (pc1, 1, [4, 8]),
+    PC2: (pc2, 2, [5]),
+    PC3: (pc3, 3, [5]),
+    PC4: (pc4, 4, [7]),
+    PC5: (pc5, 5, [7]),
+    PC6: (pc6, 6, [2]),
+    PC7: (pc7, 7, [2]),
+    PC8: (pc8, 8, [2]),
+    PC9: (pc9, 9, [0, 2, 4]),
+    PC10: (pc10, 10, [6, 7, 8]),
+    PC11: (pc11, 11, [6, 7, 8]),
+    PC12: (pc12, 12, [6, 7]),
+    PC13: (pc13, 13, []),
+    PC14: (pc14, 14, []),
+    PC15: (pc15, 15, []),
+]);
+
+#[cfg(any(feature = "at32f402", feature = "at32f405"))]
+gpio!(GPIOD, gpiod, PD, 'D', PDn, [
+    PD2: (pd2, 2, [2]),
+]);
+
+#[cfg(any(feature = "at32f402", feature = "at32f405"))]
+gpio!(GPIOF, gpiof, PF, 'F', PFn, [
+    PF0: (pf0, 0, [4]),
+    PF1: (pf1, 1, [4]),
+    PF4: (pf4, 4, []),
+    PF5: (pf5, 5, []),
+    PF6: (pf6, 6, []),
+    PF7: (pf7, 7, []),
+    PF11: (pf11, 11, []),
+]);
+
+#[cfg(feature = "at32f423")]
+gpio!(GPIOA, gpioa, PA, 'A', PAn, [
+    PA0: (pa0, 0, [1, 2, 7, 8]),
+    PA1: (pa1, 1, [1, 2, 7, 8]),
+    PA2: (pa2, 2, [1, 2, 3, 7]),
+    PA3: (pa3, 3, [1, 2, 3, 7]),
+    PA4: (pa4, 4, [5, 6, 7]),
+    PA5: (pa5, 5, [1, 5]),
+    PA6: (pa6, 6, [1, 2, 5, 9]),
+    PA7: (pa7, 7, [1, 2, 5, 9]),
+    PA8: (pa8, 8, [0, 1, 4, 7, 10]),
+    PA9: (pa9, 9, [1, 4, 7, 10]),
+    PA10: (pa10, 10, [1, 7, 10]),
+    PA11: (pa11, 11, [1, 7, 9]),
+    PA12: (pa12, 12, [1, 7, 9]),
+    PA13: (pa13, 13, [0], super::Debugger), // SWDIO, PullUp VeryHigh speed
+    PA14: (pa14, 14, [0], super::Debugger), // SWCLK, PullDown
+    PA15: (pa15, 15, [0, 1, 5, 6]),
+]);
+
+#[cfg(feature = "at32f423")]
+gpio!(GPIOB, gpiob, PB, 'B', PBn, [
+    PB0: (pb0, 0, [1, 2]),
+    PB1: (pb1, 1, [1, 2]),
+    PB2: (pb2, 2, [1]),
+    PB3: (pb3, 3, [0, 1, 5, 6, 8]),
+    PB4: (pb4, 4, [0, 2, 5, 6, 8]),
+    PB5: (pb5, 5, [2, 4, 5, 6, 8, 9]),
+    PB6: (pb6, 6, [2, 4, 7, 9]),
+    PB7: (pb7, 7, [2, 4, 7]),
+    PB8: (pb8, 8, [1, 2, 3, 4, 9]),
+    PB9: (pb9, 9, [1, 2, 3, 4, 5, 8, 9]),
+    PB10: (pb10, 10, [1, 4, 5, 7]),
+    PB11: (pb11, 11, [1, 4, 7]),
+    PB12: (pb12, 12, [1, 4, 5, 7, 9]),
+    PB13: (pb13, 13, [1, 5, 7, 9]),
+    PB14: (pb14, 14, [1, 5, 7, 9]),
+    PB15: (pb15, 15, [1, 5, 9]),
+]);
+
+#[cfg(feature = "at32f423")]
+gpio!(GPIOC, gpioc, PC, 'C', PCn, [
+    PC0: (pc0, 0, []),
+    PC1: (pc1, 1, []),
+    PC2: (pc2, 2, [5]),
+    PC3: (pc3, 3, [5]),
+    PC4: (pc4, 4, []),
+    PC5: (pc5, 5, []),
+    PC6: (pc6, 6, [2, 8]),
+    PC7: (pc7, 7, [2, 8]),
+    PC8: (pc8, 8, [2, 8]),
+    PC9: (pc9, 9, [0, 2, 4]),
+    PC10: (pc10, 10, [6, 7, 8]),
+    PC11: (pc11, 11, [6, 7, 8]),
+    PC12: (pc12, 12, [6, 7, 8]),
+    PC13: (pc13, 13, []),
+    PC14: (pc14, 14, []),
+    PC15: (pc15, 15, []),
+]);
+
+#[cfg(feature = "at32f423")]
+gpio!(GPIOD, gpiod, PD, 'D', PDn, [
+    PD0: (pd0, 0, [9]),
+    PD1: (pd1, 1, [9]),
+    PD2: (pd2, 2, [2, 8]),
+    PD3: (pd3, 3, [5, 7]),
+    PD4: (pd4, 4, [7]),
+    PD5: (pd5, 5, [7]),
+    PD6: (pd6, 6, [5, 7]),
+    PD7: (pd7, 7, [7]),
+    PD8: (pd8, 8, [7]),
+    PD9: (pd9, 9, [7]),
+    PD10: (pd10, 10, [7]),
+    PD11: (pd11, 11, [7]),
+    PD12: (pd12, 12, [2, 7]),
+    PD13: (pd13, 13, [2]),
+    PD14: (pd14, 14, [2]),
+    PD15: (pd15, 15, [2]),
+]);
+
+#[cfg(feature = "at32f423")]
+gpio!(GPIOE, gpioe, PE, 'E', PEn, [
+    PE0: (pe0, 0, [2, 8]),
+    PE1: (pe1, 1, [8]),
+    PE2: (pe2, 2, []),
+    PE3: (pe3, 3, []),
+    PE4: (pe4, 4, []),
+    PE5: (pe5, 5, [3]),
+    PE6: (pe6, 6, [3]),
+    PE7: (pe7, 7, [1, 8]),
+    PE8: (pe8, 8, [1, 8]),
+    PE9: (pe9, 9, [1]),
+    PE10: (pe10, 10, [1]),
+    PE11: (pe11, 11, [1]),
+    PE12: (pe12, 12, [1]),
+    PE13: (pe13, 13, [1]),
+    PE14: (pe14, 14, [1]),
+    PE15: (pe15, 15, [1]),
+]);
+
+#[cfg(feature = "at32f423")]
+gpio!(GPIOF, gpiof, PF, 'F', PFn, [
+    PF0: (pf0, 0, [4]),
+    PF1: (pf1, 1, [4]),
+    PF2: (pf2, 2, []),
+    PF4: (pf4, 4, []),
+    PF5: (pf5, 5, []),
+    PF6: (pf6, 6, [3]),
+    PF7: (pf7, 7, [3]),
+    PF8: (pf8, 8, [9]),
+    PF9: (pf9, 9, [9]),
+    PF10: (pf10, 10, []),
+    PF11: (pf11, 11, []),
+]);
+
+#[cfg(feature = "at32f423")]
+gpio!(GPIOH, gpioh, PH, 'H', PHn, [
+    PH0: (ph0, 0, []),
+    PH1: (ph1, 1, []),
+]);
+
+#[cfg(feature = "at32f425")]
+gpio!(GPIOA, gpioa, PA, 'A', PAn, [
+    PA0: (pa0, 0, [1, 2, 4]),
+    PA1: (pa1, 1, [1, 2, 4]),
+    PA2: (pa2, 2, [0, 1, 2]),
+    PA3: (pa3, 3, [0, 1, 2]),
+    PA4: (pa4, 4, [0, 1, 4]),
+    PA5: (pa5, 5, [0, 2]),
+    PA6: (pa6, 6, [0, 1, 2, 4, 5]),
+    PA7: (pa7, 7, [0, 1, 2, 4, 5]),
+    PA8: (pa8, 8, [0, 1, 2]),
+    PA9: (pa9, 9, [0, 1, 2, 4]),
+    PA10: (pa10, 10, [0, 1, 2, 4]),
+    PA11: (pa11, 11, [1, 2, 4, 5]),
+    PA12: (pa12, 12, [1, 2, 4, 5]),
+    PA13: (pa13, 13, [0], super::Debugger), // SWDIO, PullUp VeryHigh speed
+    PA14: (pa14, 14, [0, 1], super::Debugger), // SWCLK, PullDown
+    PA15: (pa15, 15, [0, 1, 2, 4]),
+]);
+
+#[cfg(feature = "at32f425")]
+gpio!(GPIOB, gpiob, PB, 'B', PBn, [
+    PB0: (pb0, 0, [1, 2, 4]),
+    PB1: (pb1, 1, [0, 1, 2, 4]),
+    PB2: (pb2, 2, []),
+    PB3: (pb3, 3, [0, 2]),
+    PB4: (pb4, 4, [0, 1, 5]),
+    PB5: (pb5, 5, [0, 1, 2, 3]),
+    PB6: (pb6, 6, [0, 1, 2]),
+    PB7: (pb7, 7, [0, 1, 2, 4]),
+    PB8: (pb8, 8, [1, 2, 4]),
+    PB9: (pb9, 9, [1, 2, 4, 5]),
+    PB10: (pb10, 10, [1, 2, 4, 5]),
+    PB11: (pb11, 11, [1, 2, 4]),
+    PB12: (pb12, 12, [0, 2, 3, 4, 5]),
+    PB13: (pb13, 13, [0, 2, 4, 5]),
+    PB14: (pb14, 14, [0, 1, 2, 4, 5]),
+    PB15: (pb15, 15, [0, 1, 2, 3]),
+]);
+
+#[cfg(feature = "at32f425")]
+gpio!(GPIOC, gpioc, PC, 'C', PCn, [
+    PC0: (pc0, 0, []),
+    PC1: (pc1, 1, []),
+    PC2: (pc2, 2, [1]),
+    PC3: (pc3, 3, [1]),
+    PC4: (pc4, 4, [1]),
+    PC5: (pc5, 5, [1]),
+    PC6: (pc6, 6, [0]),
+    PC7: (pc7, 7, [0]),
+    PC8: (pc8, 8, [0]),
+    PC9: (pc9, 9, [0]),
+    PC10: (pc10, 10, [0, 1]),
+    PC11: (pc11, 11, [0, 1]),
+    PC12: (pc12, 12, [0, 1]),
+    PC13: (pc13, 13, []),
+    PC14: (pc14, 14, []),
+    PC15: (pc15, 15, []),
+]);
+
+#[cfg(feature = "at32f425")]
+gpio!(GPIOD, gpiod, PD, 'D', PDn, [
+    PD2: (pd2, 2, [0]),
+]);
+
+#[cfg(feature = "at32f425")]
+gpio!(GPIOF, gpiof, PF, 'F', PFn, [
+    PF0: (pf0, 0, []),
+    PF1: (pf1, 1, []),
+    PF4: (pf4, 4, []),
+    PF5: (pf5, 5, []),
+    PF6: (pf6, 6, []),
+    PF7: (pf7, 7, []),
+    PF11: (pf11, 11, []),
+]);
+
+#[cfg(any(feature = "at32f435", feature = "at32f437"))]
+gpio!(GPIOA, gpioa, PA, 'A', PAn, [
+    PA0: (pa0, 0, [1, 2, 3, 7, 8, 11]),
+    PA1: (pa1, 1, [1, 2, 5, 7, 8, 9, 11]),
+    PA2: (pa2, 2, [1, 2, 3, 7, 11]),
+    PA3: (pa3, 3, [1, 2, 3, 7, 11]),
+    PA4: (pa4, 4, [5, 6, 7]),
+    PA5: (pa5, 5, [1, 3, 5]),
+    PA6: (pa6, 6, [1, 2, 3, 5, 9]),
+    PA7: (pa7, 7, [1, 2, 3, 5, 9, 11]),
+    PA8: (pa8, 8, [0, 1, 4, 7]),
+    PA9: (pa9, 9, [1, 4, 5, 7]),
+    PA10: (pa10, 10, [1, 5, 7]),
+    PA11: (pa11, 11, [1, 5, 7, 9]),
+    PA12: (pa12, 12, [1, 5, 7, 9]),
+    PA13: (pa13, 13, [0], super::Debugger), // SWDIO, PullUp VeryHigh speed
+    PA14: (pa14, 14, [0], super::Debugger), // SWCLK, PullDown
+    PA15: (pa15, 15, [0, 1, 5, 6, 8]),
+]);
+
+#[cfg(any(feature = "at32f435", feature = "at32f437"))]
+gpio!(GPIOB, gpiob, PB, 'B', PBn, [
+    PB0: (pb0, 0, [1, 2, 3, 11]),
+    PB1: (pb1, 1, [1, 2, 3, 9, 11]),
+    PB2: (pb2, 2, [1, 9]),
+    PB3: (pb3, 3, [0, 1, 5, 6, 8]),
+    PB4: (pb4, 4, [0, 2, 5, 6, 8]),
+    PB5: (pb5, 5, [2, 4, 5, 6, 8, 9, 11]),
+    PB6: (pb6, 6, [2, 4, 7, 8, 9, 10]),
+    PB7: (pb7, 7, [2, 4, 7, 12]),
+    PB8: (pb8, 8, [1, 2, 3, 4, 8, 9, 11, 12]),
+    PB9: (pb9, 9, [1, 2, 3, 4, 5, 8, 9, 12]),
+    PB10: (pb10, 10, [1, 4, 5, 7, 9, 11]),
+    PB11: (pb11, 11, [1, 4, 7, 11]),
+    PB12: (pb12, 12, [1, 4, 5, 7, 8, 9, 11]),
+    PB13: (pb13, 13, [1, 5, 7, 8, 9, 11]),
+    PB14: (pb14, 14, [1, 3, 5, 7, 9]),
+    PB15: (pb15, 15, [1, 3, 5, 9]),
+]);
+
+#[cfg(any(feature = "at32f435", feature = "at32f437"))]
+gpio!(GPIOC, gpioc, PC, 'C', PCn, [
+    PC0: (pc0, 0, []),
+    PC1: (pc1, 1, [11]),
+    PC2: (pc2, 2, [5, 11]),
+    PC3: (pc3, 3, [5, 11]),
+    PC4: (pc4, 4, [11]),
+    PC5: (pc5, 5, [11]),
+    PC6: (pc6, 6, [2, 3, 8, 12]),
+    PC7: (pc7, 7, [2, 3, 8, 12]),
+    PC8: (pc8, 8, [2, 3, 8, 12]),
+    PC9: (pc9, 9, [0, 2, 3, 4, 12]),
+    PC10: (pc10, 10, [6, 7, 8, 12]),
+    PC11: (pc11, 11, [6, 7, 8, 12]),
+    PC12: (pc12, 12, [6, 7, 8, 12]),
+    PC13: (pc13, 13, []),
+    PC14: (pc14, 14, []),
+    PC15: (pc15, 15, []),
+]);
+
+#[cfg(any(feature = "at32f435", feature = "at32f437"))]
+gpio!(GPIOD, gpiod, PD, 'D', PDn, [
+    PD0: (pd0, 0, [9, 12]),
+    PD1: (pd1, 1, [9, 12]),
+    PD2: (pd2, 2, [2, 8, 12]),
+    PD3: (pd3, 3, [5, 7, 12]),
+    PD4: (pd4, 4, [7, 12]),
+    PD5: (pd5, 5, [7, 12]),
+    PD6: (pd6, 6, [5, 7, 12]),
+    PD7: (pd7, 7, [7, 12]),
+    PD8: (pd8, 8, [7, 12]),
+    PD9: (pd9, 9, [7, 12]),
+    PD10: (pd10, 10, [7, 12]),
+    PD11: (pd11, 11, [7, 9, 12]),
+    PD12: (pd12, 12, [2, 7, 9, 12]),
+    PD13: (pd13, 13, [2, 9, 12]),
+    PD14: (pd14, 14, [2, 12]),
+    PD15: (pd15, 15, [2, 12]),
+]);
+
+#[cfg(any(feature = "at32f435", feature = "at32f437"))]
+gpio!(GPIOE, gpioe, PE, 'E', PEn, [
+    PE0: (pe0, 0, [2, 6, 8, 12]),
+    PE1: (pe1, 1, [6, 8, 12]),
+    PE2: (pe2, 2, [5, 6, 9, 11, 12]),
+    PE3: (pe3, 3, [6, 12]),
+    PE4: (pe4, 4, [5, 6, 12]),
+    PE5: (pe5, 5, [3, 5, 6, 12]),
+    PE6: (pe6, 6, [3, 5, 6, 12]),
+    PE7: (pe7, 7, [1, 8, 12]),
+    PE8: (pe8, 8, [1, 8, 12]),
+    PE9: (pe9, 9, [1, 12]),
+    PE10: (pe10, 10, [1, 12]),
+    PE11: (pe11, 11, [1, 5, 12]),
+    PE12: (pe12, 12, [1, 5, 12]),
+    PE13: (pe13, 13, [1, 5, 12]),
+    PE14: (pe14, 14, [1, 5, 12]),
+    PE15: (pe15, 15, [1, 12]),
+]);
+
+#[cfg(any(feature = "at32f435", feature = "at32f437"))]
+gpio!(GPIOF, gpiof, PF, 'F', PFn, [
+    PF0: (pf0, 0, [4, 12]),
+    PF1: (pf1, 1, [4, 12]),
+    PF2: (pf2, 2, [4, 6, 12]),
+    PF3: (pf3, 3, [12]),
+    PF4: (pf4, 4, [6, 12]),
+    PF5: (pf5, 5, [6, 12]),
+    PF6: (pf6, 6, [3, 6, 8, 9]),
+    PF7: (pf7, 7, [3, 6, 8, 9]),
+    PF8: (pf8, 8, [9, 10]),
+    PF9: (pf9, 9, [9, 10]),
+    PF10: (pf10, 10, [9]),
+    PF11: (pf11, 11, []),
+    PF12: (pf12, 12, [6, 12]),
+    PF13: (pf13, 13, [6, 12]),
+    PF14: (pf14, 14, [6, 12]),
+    PF15: (pf15, 15, [6, 12]),
+]);
+
+#[cfg(any(feature = "at32f435", feature = "at32f437"))]
+gpio!(GPIOG, gpiog, PG, 'G', PGn, [
+    PG0: (pg0, 0, [12]),
+    PG1: (pg1, 1, [12]),
+    PG2: (pg2, 2, [12]),
+    PG3: (pg3, 3, [6, 12]),
+    PG4: (pg4, 4, [12]),
+    PG5: (pg5, 5, [6, 12]),
+    PG6: (pg6, 6, [10]),
+    PG7: (pg7, 7, [8]),
+    PG8: (pg8, 8, [8, 11]),
+    PG9: (pg9, 9, [8, 12]),
+    PG10: (pg10, 10, [12]),
+    PG11: (pg11, 11, [11]),
+    PG12: (pg12, 12, [8, 12]),
+    PG13: (pg13, 13, [8, 11]),
+    PG14: (pg14, 14, [8, 11]),
+    PG15: (pg15, 15, [8]),
+]);
+
+#[cfg(any(feature = "at32f435", feature = "at32f437"))]
+gpio!(GPIOH, gpioh, PH, 'H', PHn, [
+    PH0: (ph0, 0, []),
+    PH1: (ph1, 1, []),
+    PH2: (ph2, 2, [11]),
+    PH3: (ph3, 3, [11]),
+    PH4: (ph4, 4, [4]),
+    PH5: (ph5, 5, [4]),
+    PH6: (ph6, 6, [4, 9, 11]),
+    PH7: (ph7, 7, [4, 11]),
+    PH8: (ph8, 8, [4]),
+    PH9: (ph9, 9, [4, 9]),
+    PH10: (ph10, 10, [2]),
+    PH11: (ph11, 11, [2]),
+    PH12: (ph12, 12, [2]),
+    PH13: (ph13, 13, [3, 9]),
+    PH14: (ph14, 14, [3, 9]),
+    PH15: (ph15, 15, [3]),
+]);
@@ -134,6 +134,18 @@ macro_rules! i2c {
 i2c! { pac::I2C1: I2c1 }
 i2c! { pac::I2C2: I2c2 }
 
+// The driver handles the STS1/STS2 I2C block. I2C3 of AT32F402/405/423/435/437 is the
+// newer block of those families, so it is only registered on the AT32F403A/407.
+#[cfg(feature = "i2c3")]
+#[cfg(not(any(
+    feature = "at32f402",
+    feature = "at32f405",
+    feature = "at32f423",
+    feature = "at32f435",
+    feature = "at32f437",
+)))]
+i2c! { pac::I2C3: I2c3 }
+
 pub trait I2cExt: Sized + Instance {
     fn i2c(
         self,
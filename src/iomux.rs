@@ -1 +1,168 @@
+//! # Alternate function remapping
+//!
+//! On legacy-gpio devices (F403/F403A/F407/F413/F415/A403A) alternate function
+//! outputs are not selected per pin. Instead each peripheral has a fixed set of
+//! pin groups and the `IOMUX` block chooses which group is routed to the pins.
+//!
+//! The `A` parameter of `Alternate<A>` in the alternate function tables of these
+//! devices is a mask of the remap modes that route the signal to the pin: bit `n`
+//! is set if the pin carries the signal while the remap field of the peripheral in
+//! `IOMUX_REMAP` holds `n`. Pins shared by several modes have several bits set, e.g.
+//! TMR2 CH3 on `PA2<0b0011>` (no remap and partial remap 1) or on `PB10<0b1100>`
+//! (partial remap 2 and full remap). Peripherals without [`Remap`] use the modes of
+//! their field in the reference manual the same way.
+//!
+//! Select a mode whose bit is set for all used pins before creating the driver:
+//!
+//! ```
+//! let mut iomux = dp.IOMUX.constrain();
+//! iomux.disable_jtag();
+//! pac::USART1::remap(&mut iomux, 1); // TX = PB6<0b10>, RX = PB7<0b10>
+//! assert!(pac::USART1::routes(&iomux, 0b10));
+//! ```
 
+use crate::crm::{Enable, Reset};
+use crate::pac::{self, IOMUX};
+
+/// Extension trait that constrains the `IOMUX` peripheral
+pub trait IomuxExt {
+    /// Constrains the `IOMUX` peripheral so it plays nicely with the other abstractions
+    fn constrain(self) -> Iomux;
+}
+
+impl IomuxExt for IOMUX {
+    fn constrain(self) -> Iomux {
+        unsafe {
+            // Enable clock.
+            IOMUX::enable_unchecked();
+            IOMUX::reset_unchecked();
+        }
+        Iomux {
+            _iomux: self,
+            swjtag: 0,
+        }
+    }
+}
+
+/// Constrained `IOMUX` peripheral
+pub struct Iomux {
+    _iomux: IOMUX,
+    swjtag: u32,
+}
+
+impl Iomux {
+    /// Disables JTAG-DP and keeps SW-DP, releasing PA15, PB3 and PB4
+    pub fn disable_jtag(&mut self) {
+        self.set_swjtag(0b010);
+    }
+
+    /// Disables both JTAG-DP and SW-DP, releasing all debug pins
+    pub fn disable_debug(&mut self) {
+        self.set_swjtag(0b100);
+    }
+
+    /// Routes GPIO port `port` (0 for GPIOA, 1 for GPIOB, ...) to EXINT line `line`
+    pub(crate) fn set_exint_source(&mut self, line: u8, port: u8) {
+        let offset = 4 * (line % 4);
+        let mask = !(0xf << offset);
+        let value = (port as u32) << offset;
+        let iomux = unsafe { &*IOMUX::ptr() };
+        unsafe {
+            match line {
+                0..=3 => iomux
+                    .exintc1()
+                    .modify(|r, w| w.bits((r.bits() & mask) | value)),
+                4..=7 => iomux
+                    .exintc2()
+                    .modify(|r, w| w.bits((r.bits() & mask) | value)),
+                8..=11 => iomux
+                    .exintc3()
+                    .modify(|r, w| w.bits((r.bits() & mask) | value)),
+                12..=15 => iomux
+                    .exintc4()
+                    .modify(|r, w| w.bits((r.bits() & mask) | value)),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    fn set_swjtag(&mut self, bits: u32) {
+        self.swjtag = bits << 24;
+        self.modify_remap(24, 3, bits);
+    }
+
+    fn modify_remap(&mut self, offset: u8, width: u8, bits: u32) {
+        let mask = ((1 << width) - 1) << offset;
+        // SWJTAG bits are write-only and read back as zero, so they are restored on every write
+        let swjtag = self.swjtag;
+        unsafe {
+            (*IOMUX::ptr()).remap().modify(|r, w| {
+                w.bits((r.bits() & !mask & !(0b111 << 24)) | ((bits << offset) & mask) | swjtag)
+            });
+        }
+    }
+}
+
+/// Peripherals that can be routed to another set of pins
+pub trait Remap: crate::Sealed {
+    #[doc(hidden)]
+    const OFFSET: u8;
+    #[doc(hidden)]
+    const WIDTH: u8;
+    /// Mask of the valid remap modes, bit `n` for mode `n`
+    const MODES: u8;
+
+    /// Selects remap mode `mode` for this peripheral
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mode` is reserved for this peripheral, see [`MODES`](Self::MODES).
+    fn remap(iomux: &mut Iomux, mode: u8) {
+        assert!(mode < 8 && Self::MODES & (1 << mode) != 0);
+        iomux.modify_remap(Self::OFFSET, Self::WIDTH, mode as u32);
+    }
+
+    /// Returns the remap mode currently selected for this peripheral
+    fn mode(_iomux: &Iomux) -> u8 {
+        let remap = unsafe { (*IOMUX::ptr()).remap().read().bits() };
+        ((remap >> Self::OFFSET) & ((1 << Self::WIDTH) - 1)) as u8
+    }
+
+    /// Returns `true` if the selected mode routes the pins with the alternate function
+    /// mask `pins`, e.g. `0b0011` of `PA2<0b0011>`
+    fn routes(iomux: &Iomux, pins: u8) -> bool {
+        pins & (1 << Self::mode(iomux)) != 0
+    }
+}
+
+macro_rules! remap {
+    ($($(#[$attr:meta])* $PER:ident => ($offset:literal, $width:literal, $modes:literal),)+) => {
+        $(
+            $(#[$attr])*
+            impl Remap for pac::$PER {
+                const OFFSET: u8 = $offset;
+                const WIDTH: u8 = $width;
+                const MODES: u8 = $modes;
+            }
+        )+
+    };
+}
+
+remap! {
+    SPI1 => (0, 1, 0b11),
+    I2C1 => (1, 1, 0b11),
+    USART1 => (2, 1, 0b11),
+    USART2 => (3, 1, 0b11),
+    #[cfg(feature = "usart3")]
+    USART3 => (4, 2, 0b1011),
+    #[cfg(feature = "tmr1")]
+    TMR1 => (6, 2, 0b1011),
+    #[cfg(feature = "tmr2")]
+    TMR2 => (8, 2, 0b1111),
+    #[cfg(feature = "tmr3")]
+    TMR3 => (10, 2, 0b1101),
+    #[cfg(feature = "tmr4")]
+    TMR4 => (12, 1, 0b11),
+    #[cfg(feature = "can1")]
+    CAN1 => (13, 2, 0b1101),
+}
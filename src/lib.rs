@@ -101,6 +101,7 @@ pub use crate::pac::interrupt;
 pub mod adc;
 pub mod bb;
 pub mod crm;
+pub mod dma;
 pub mod flash;
 pub mod gpio;
 pub mod i2c;
@@ -114,6 +115,11 @@ pub mod uart;
 
 #[cfg(feature = "iomux")]
 pub mod iomux;
+#[cfg(feature = "new-gpio")]
+pub mod scfg;
+
+#[cfg(feature = "async")]
+mod waker;
 
 mod sealed {
     pub trait Sealed {}
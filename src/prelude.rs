@@ -47,8 +47,9 @@ pub use fugit::RateExtU32 as _fugit_RateExtU32;
 pub use crate::can::CanExt as _at32f4xx_hal_can_CanExt;
 #[cfg(feature = "dac")]
 pub use crate::dac::DacExt as _at32f4xx_hal_dac_DacExt;
+pub use crate::dma::DmaExt as _at32f4xx_hal_dma_DmaExt;
 // pub use crate::gpio::outport::OutPort as _;
-// pub use crate::gpio::ExtiPin as _at32f4xx_hal_gpio_ExtiPin;
+pub use crate::gpio::ExtiPin as _at32f4xx_hal_gpio_ExtiPin;
 
 pub use crate::flash::FlashExt as _at32f4xx_hal_flash_FlashExt;
 pub use crate::gpio::GpioExt as _at32f4xx_hal_gpio_GpioExt;
@@ -57,6 +58,10 @@ pub use crate::gpio::GpioExt as _at32f4xx_hal_gpio_GpioExt;
 // pub use crate::i2c::dma::I2CMasterWriteDMA as _at32f4xx_hal_i2c_dma_I2CMasterWriteDMA;
 // pub use crate::i2c::dma::I2CMasterWriteReadDMA as _at32f4xx_hal_i2c_dma_I2CMasterWriteReadDMA;
 pub use crate::i2c::I2cExt as _at32f4xx_hal_i2c_I2cExt;
+#[cfg(feature = "iomux")]
+pub use crate::iomux::IomuxExt as _at32f4xx_hal_iomux_IomuxExt;
+#[cfg(feature = "iomux")]
+pub use crate::iomux::Remap as _at32f4xx_hal_iomux_Remap;
 // pub use crate::i2s::I2sExt as _at32f4xx_hal_i2s_I2sExt;
 pub use crate::crm::CrmExt as _at32f4xx_hal_crm_CrmExt;
 pub use crate::qei::QeiExt as _at32f4xx_hal_QeiExt;
@@ -69,15 +74,20 @@ pub use crate::serial::SerialExt as _at32f4xx_hal_serial_SerialExt;
 pub use crate::serial::TxISR as _at32f4xx_hal_serial_TxISR;
 pub use crate::serial::TxListen as _at32f4xx_hal_serial_TxListen;
 // pub use crate::spi::SpiExt as _at32f4xx_hal_spi_SpiExt;
-// pub use crate::syscfg::SysCfgExt as _at32f4xx_hal_syscfg_SysCfgExt;
+#[cfg(feature = "new-gpio")]
+pub use crate::scfg::ScfgExt as _at32f4xx_hal_scfg_ScfgExt;
 pub use crate::time::U32Ext as _at32f4xx_hal_time_U32Ext;
+pub use crate::timer::CaptureExt as _at32f4xx_hal_timer_CaptureExt;
+pub use crate::timer::HallSensorExt as _at32f4xx_hal_timer_HallSensorExt;
 #[cfg(feature = "rtic")]
 pub use crate::timer::MonoTimer64Ext as _;
 #[cfg(feature = "rtic")]
 pub use crate::timer::MonoTimerExt as _;
-// pub use crate::timer::PwmExt as _at32f4xx_hal_timer_PwmExt;
-// #[cfg(feature = "rtic")]
-// pub use crate::timer::SysMonoTimerExt as _at32f4xx_hal_timer_SysMonoTimerExt;
+pub use crate::timer::OnePulseExt as _at32f4xx_hal_timer_OnePulseExt;
+pub use crate::timer::OutputCompareExt as _at32f4xx_hal_timer_OutputCompareExt;
 pub use crate::timer::PwmExt as _at32f4xx_hal_timer_PwmExt;
+pub use crate::timer::StepperExt as _at32f4xx_hal_timer_StepperExt;
+#[cfg(feature = "rtic")]
+pub use crate::timer::SysMonoTimerExt as _at32f4xx_hal_timer_SysMonoTimerExt;
 pub use crate::timer::SysTimerExt as _at32f4xx_hal_timer_SysCounterExt;
 pub use crate::timer::TimerExt as _at32f4xx_hal_timer_TimerExt;
@@ -1,9 +1,30 @@
 //! # Quadrature Encoder Interface
+//!
+//! The timer counts the edges of the two encoder signals. The raw count wraps at the
+//! auto-reload value, [`Qei::position`] extends it to 64 bits as long as the update
+//! interrupt is serviced with [`Qei::on_update`]:
+//!
+//! ```rust
+//! let mut qei = dp.TMR3.qei((gpioa.pa6, gpioa.pa7));
+//! qei.configure(QeiConfig::new().mode(EncoderMode::Both).filter(5));
+//! qei.listen(Event::Update);
+//!
+//! // in the TMR3 interrupt
+//! qei.on_update();
+//! let pos = qei.position();
+//! ```
+//!
+//! The index (Z) signal of the encoder either goes to channel 3, where the count is
+//! latched by hardware, see [`Qei::index_pin`], or to an EXINT line whose interrupt
+//! calls [`Qei::on_index`].
 use crate::{
     crm,
     gpio::Input,
     pac,
-    timer::{CPin, General},
+    timer::{
+        CPin, CaptureEdge, CapturePrescaler, CaptureSource, Event, General, WithCapture,
+        WithPlusMode,
+    },
 };
 
 pub enum Direction {
@@ -13,6 +34,75 @@ pub enum Direction {
     Upcounting,
 }
 
+/// Encoder signals whose edges are counted
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum EncoderMode {
+    /// Count edges of channel 1 only, 2 counts per encoder cycle
+    Ti1 = 0b001,
+    /// Count edges of channel 2 only, 2 counts per encoder cycle
+    Ti2 = 0b010,
+    /// Count edges of both channels, 4 counts per encoder cycle
+    Both = 0b011,
+}
+
+/// Encoder input configuration
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct QeiConfig {
+    pub mode: EncoderMode,
+    /// Digital filter of both inputs, `0` disables filtering
+    pub filter: u8,
+    /// Invert channel 1 input, `false` keeps the polarity set up by [`Qei::new`]
+    pub invert_ch1: bool,
+    /// Invert channel 2 input, which reverses the counting direction
+    pub invert_ch2: bool,
+}
+
+impl Default for QeiConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl QeiConfig {
+    pub const fn new() -> Self {
+        Self {
+            mode: EncoderMode::Ti1,
+            filter: 3,
+            invert_ch1: false,
+            invert_ch2: false,
+        }
+    }
+
+    pub const fn mode(mut self, mode: EncoderMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub const fn filter(mut self, filter: u8) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub const fn invert(mut self, ch1: bool, ch2: bool) -> Self {
+        self.invert_ch1 = ch1;
+        self.invert_ch2 = ch2;
+        self
+    }
+}
+
+/// Action on the index (Z) pulse
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum IndexMode {
+    /// The position becomes zero at the index pulse
+    Reset,
+    /// The position at the index pulse is stored, see [`Qei::index_position`]
+    Latch,
+}
+
 pub trait QeiExt: Sized + Instance {
     fn qei(
         self,
@@ -39,6 +129,12 @@ impl<TMR: Instance> QeiExt for TMR {
 pub struct Qei<TMR: Instance> {
     tmr: TMR,
     pins: (<TMR as CPin<0>>::Ch<Input>, <TMR as CPin<1>>::Ch<Input>),
+    /// Counter wraps since the start, negative for underflows
+    wraps: i64,
+    /// Subtracted from the extended count to get the position
+    offset: i64,
+    index_mode: IndexMode,
+    index_position: Option<i64>,
 }
 
 impl<TMR: Instance> Qei<TMR> {
@@ -58,7 +154,22 @@ impl<TMR: Instance> Qei<TMR> {
 
         let pins1 = (pins.0.into(), pins.1.into());
         tmr.setup_qei();
-        Qei { tmr, pins: pins1 }
+        Qei {
+            tmr,
+            pins: pins1,
+            wraps: 0,
+            offset: 0,
+            index_mode: IndexMode::Reset,
+            index_position: None,
+        }
+    }
+
+    /// Apply the encoder input configuration
+    pub fn configure(&mut self, config: QeiConfig) -> &mut Self {
+        self.tmr.disable_counter();
+        self.tmr.configure_qei(&config);
+        self.tmr.enable_counter();
+        self
     }
 
     /// Releases the TMR peripheral and QEI pins
@@ -89,11 +200,167 @@ impl<TMR: Instance> Qei<TMR> {
             Direction::Downcounting
         }
     }
+
+    /// Starts listening for an `event`
+    ///
+    /// [`Event::Update`] is needed for [`position`](Self::position), [`Event::C3`] for
+    /// the index pulse on channel 3.
+    pub fn listen(&mut self, event: Event) {
+        self.tmr.listen_interrupt(event, true);
+    }
+
+    /// Stops listening for an `event`
+    pub fn unlisten(&mut self, event: Event) {
+        self.tmr.listen_interrupt(event, false);
+    }
+
+    /// Account for a counter overflow or underflow, call this from the update interrupt
+    pub fn on_update(&mut self) {
+        if self.update_pending() {
+            self.tmr.clear_interrupt_flag(Event::Update);
+            let wrap = self.wrap_direction(self.count().into());
+            self.wraps += wrap;
+        }
+    }
+
+    /// Get the position extended to 64 bits
+    pub fn position(&self) -> i64 {
+        let (count, wrap) = self.snapshot();
+        self.extend_with(count, wrap)
+    }
+
+    /// Set the current position
+    pub fn set_position(&mut self, position: i64) {
+        let current = self.position();
+        self.offset += current - position;
+    }
+
+    /// Set the action on the index pulse
+    pub fn set_index_mode(&mut self, mode: IndexMode) {
+        self.index_mode = mode;
+    }
+
+    /// Position at the last index pulse in [`IndexMode::Latch`]
+    pub fn index_position(&self) -> Option<i64> {
+        self.index_position
+    }
+
+    /// Handle an index pulse routed to an EXINT line, call this from its interrupt
+    ///
+    /// The current count is used, so the interrupt latency limits the accuracy.
+    /// Returns the position at the index pulse.
+    pub fn on_index(&mut self) -> i64 {
+        let count = self.count().into();
+        self.index(count)
+    }
+
+    fn index(&mut self, count: u32) -> i64 {
+        let position = self.extend(count);
+        match self.index_mode {
+            IndexMode::Reset => self.offset += position,
+            IndexMode::Latch => self.index_position = Some(position),
+        }
+        position
+    }
+
+    /// Position for a `count` read or latched before now
+    fn extend(&self, count: u32) -> i64 {
+        let (_, mut wrap) = self.snapshot();
+        // A count from before the pending wrap is at the other end of the range
+        if wrap != 0 && self.wrap_direction(count) != wrap {
+            wrap = 0;
+        }
+        self.extend_with(count, wrap)
+    }
+
+    fn extend_with(&self, count: u32, wrap: i64) -> i64 {
+        (self.wraps + wrap) * Self::period() + count as i64 - self.offset
+    }
+
+    /// Current count and the wrap not yet counted by [`on_update`](Self::on_update)
+    ///
+    /// The update flag is read before and after the count, so the count is known to be
+    /// from before or after the wrap.
+    fn snapshot(&self) -> (u32, i64) {
+        loop {
+            let pending = self.update_pending();
+            let count = self.count().into();
+            if self.update_pending() == pending {
+                let wrap = if pending {
+                    self.wrap_direction(count)
+                } else {
+                    0
+                };
+                return (count, wrap);
+            }
+        }
+    }
+
+    fn update_pending(&self) -> bool {
+        self.tmr.get_interrupt_flag().contains(Event::Update)
+    }
+
+    /// +1 for an overflow, -1 for an underflow, told by which end the count is close to
+    fn wrap_direction(&self, count: u32) -> i64 {
+        if (count as i64) < Self::period() / 2 {
+            1
+        } else {
+            -1
+        }
+    }
+
+    fn period() -> i64 {
+        TMR::read_auto_reload() as i64 + 1
+    }
+}
+
+impl<TMR: Instance + CPin<2> + WithCapture> Qei<TMR> {
+    /// Connect the index (Z) signal to channel 3, the count is latched by hardware
+    /// on `edge`
+    ///
+    /// Like the encoder pins, the pin is switched to the input mode of its alternate
+    /// function. A pull resistor configured on an input pin before is kept.
+    ///
+    /// Listen to [`Event::C3`] and call [`on_index_capture`](Self::on_index_capture)
+    /// from the interrupt.
+    pub fn index_pin(&mut self, pin: impl Into<<TMR as CPin<2>>::Ch<Input>>, edge: CaptureEdge) {
+        let _pin = pin.into();
+        TMR::enable_channel(2, false);
+        TMR::set_input_mode(2, CaptureSource::Direct, 0, CapturePrescaler::Div1);
+        TMR::set_capture_edge(2, edge);
+        TMR::clear_capture_flags(2);
+        TMR::enable_channel(2, true);
+    }
+
+    /// Handle an index pulse captured on channel 3
+    ///
+    /// Returns the position at the index pulse, `None` if there was no pulse.
+    pub fn on_index_capture(&mut self) -> Option<i64> {
+        if !TMR::is_captured(2) {
+            return None;
+        }
+        let count = TMR::read_cc_value(2);
+        TMR::clear_capture_flags(2);
+        Some(self.index(count))
+    }
+}
+
+impl<TMR: Instance + WithPlusMode> Qei<TMR> {
+    /// Count over 32 bits instead of 16 bits (plus mode)
+    pub fn enable_32bit(&mut self, b: bool) -> &mut Self {
+        self.tmr.disable_counter();
+        self.tmr.enable_plus_mode(b);
+        self.tmr.set_auto_reload(TMR::max_auto_reload()).unwrap();
+        self.tmr.enable_counter();
+        self
+    }
 }
 
 pub trait Instance: crate::Sealed + crm::Enable + crm::Reset + General + CPin<0> + CPin<1> {
     fn setup_qei(&mut self);
 
+    fn configure_qei(&mut self, config: &QeiConfig);
+
     fn read_direction(&self) -> bool;
 }
 
@@ -116,15 +383,40 @@ macro_rules! hal {
                     .write(|w| w.c1p().high().c2p().high().c1en().enable().c2en().enable());
                 // enable and configure to capture on rising edge
                 self.stctrl().modify(|_, w| w.smsel().encoder_a());
-                self.set_auto_reload(<$TMR as General>::Width::MAX as u32)
+                self.set_auto_reload(<$TMR as General>::max_auto_reload())
                     .unwrap();
                 self.set_prescaler(2);
                 self.ctrl1().modify(|_, w| w.prben().enable());
                 self.enable_counter();
             }
 
+            fn configure_qei(&mut self, config: &QeiConfig) {
+                // CxDF is bits 7:4 and 15:12
+                let filter = (config.filter & 0xf) as u32;
+                self.cm1_input().modify(|r, w| unsafe {
+                    w.bits((r.bits() & !0xf0f0) | (filter << 4) | (filter << 12))
+                });
+                // Not inverted is the polarity set up by `setup_qei`
+                self.cctrl().modify(|_, w| {
+                    let w = if config.invert_ch1 {
+                        w.c1p().low()
+                    } else {
+                        w.c1p().high()
+                    };
+                    if config.invert_ch2 {
+                        w.c2p().low()
+                    } else {
+                        w.c2p().high()
+                    }
+                });
+                // SMSEL is bits 2:0
+                self.stctrl()
+                    .modify(|r, w| unsafe { w.bits((r.bits() & !0b111) | config.mode as u32) });
+            }
+
             fn read_direction(&self) -> bool {
-                true
+                // CNT_DIR is set while counting down
+                self.ctrl1().read().bits() & (1 << 4) == 0
             }
         }
     };
@@ -0,0 +1,62 @@
+//! System configuration controller (SCFG)
+
+use core::ops::Deref;
+
+use crate::crm::{Enable, Reset};
+use crate::pac::SCFG;
+
+/// Extension trait that constrains the `SCFG` peripheral
+pub trait ScfgExt {
+    /// Constrains the `SCFG` peripheral so it plays nicely with the other abstractions
+    fn constrain(self) -> Scfg;
+}
+
+impl ScfgExt for SCFG {
+    fn constrain(self) -> Scfg {
+        unsafe {
+            // Enable clock.
+            SCFG::enable_unchecked();
+            SCFG::reset_unchecked();
+        }
+
+        Scfg(self)
+    }
+}
+
+/// Constrained `SCFG` peripheral
+pub struct Scfg(pub(crate) SCFG);
+
+impl Deref for Scfg {
+    type Target = SCFG;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Scfg {
+    /// Routes GPIO port `port` (0 for GPIOA, 1 for GPIOB, ...) to EXINT line `line`
+    pub(crate) fn set_exint_source(&mut self, line: u8, port: u8) {
+        let offset = 4 * (line % 4);
+        let mask = !(0xf << offset);
+        let value = (port as u32) << offset;
+        unsafe {
+            match line {
+                0..=3 => self
+                    .exintc1()
+                    .modify(|r, w| w.bits((r.bits() & mask) | value)),
+                4..=7 => self
+                    .exintc2()
+                    .modify(|r, w| w.bits((r.bits() & mask) | value)),
+                8..=11 => self
+                    .exintc3()
+                    .modify(|r, w| w.bits((r.bits() & mask) | value)),
+                12..=15 => self
+                    .exintc4()
+                    .modify(|r, w| w.bits((r.bits() & mask) | value)),
+                _ => unreachable!(),
+            }
+        }
+    }
+}
@@ -227,6 +227,15 @@ halUsart! { pac::USART2, Serial2, Rx2, Tx2 }
 #[cfg(feature = "usart3")]
 halUsart! { pac::USART3, Serial3, Rx3, Tx3 }
 
+#[cfg(feature = "usart4")]
+halUsart! { pac::USART4, Serial4, Rx4, Tx4 }
+
+#[cfg(feature = "usart5")]
+halUsart! { pac::USART5, Serial5, Rx5, Tx5 }
+
+#[cfg(feature = "usart6")]
+halUsart! { pac::USART6, Serial6, Rx6, Tx6 }
+
 impl<UART: CommonPins> Rx<UART, u8> {
     pub(crate) fn with_u16_data(self) -> Rx<UART, u16> {
         Rx::new(self.pin)
@@ -17,6 +17,8 @@ pub(crate) use crate::pac::uart4::RegisterBlock as RegisterBlockUart;
 pub(crate) use crate::pac::usart1::RegisterBlock as RegisterBlockUsart;
 
 impl crate::Sealed for RegisterBlockUsart {}
+#[cfg(feature = "uart4")]
+impl crate::Sealed for RegisterBlockUart {}
 
 // Implemented by all USART/UART instances
 pub trait Instance: crate::Sealed + crm::Enable + crm::Reset + crm::BusClock + CommonPins {
@@ -303,6 +305,9 @@ macro_rules! uartCommon {
 
 uartCommon! { RegisterBlockUsart }
 
+#[cfg(feature = "uart4")]
+uartCommon! { RegisterBlockUart }
+
 impl<UART: Instance, WORD> RxISR for Serial<UART, WORD>
 where
     Rx<UART, WORD>: RxISR,
@@ -5,7 +5,10 @@ use cortex_m::peripheral::syst::SystClkSource;
 use cortex_m::peripheral::SYST;
 
 use crate::bb;
+use crate::dma::traits::{DMASet, PeriAddress};
+use crate::dma::{MemoryToPeripheral, PeripheralToMemory};
 use crate::pac;
+use core::marker::PhantomData;
 
 use crate::crm::{self, Clocks};
 use fugit::HertzU32 as Hertz;
@@ -16,6 +19,30 @@ pub mod delay;
 pub use delay::*;
 pub mod pwm;
 pub use pwm::*;
+pub mod capture;
+pub use capture::*;
+pub mod capture_stream;
+pub use capture_stream::*;
+pub mod one_pulse;
+pub use one_pulse::*;
+pub mod output_compare;
+pub use output_compare::*;
+pub mod motor_control;
+pub use motor_control::*;
+pub mod slave;
+pub use slave::*;
+pub mod stepper;
+pub use stepper::*;
+pub mod hall;
+pub use hall::*;
+pub mod uptime;
+pub use uptime::*;
+#[cfg(feature = "rtic")]
+pub mod monotonic;
+#[cfg(feature = "rtic")]
+pub use monotonic::*;
+#[cfg(feature = "_time-driver")]
+pub mod time_driver;
 
 pub mod hal;
 pub use hal::*;
@@ -53,6 +80,7 @@ pub const C4: u8 = 3;
 
 /// Enum for IO polarity
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Polarity {
     ActiveHigh,
     ActiveLow,
@@ -72,15 +100,91 @@ bitflags::bitflags! {
         const C2 = 1 << 2;
         const C3 = 1 << 3;
         const C4 = 1 << 4;
+        const Com = 1 << 5;
+        const Trigger = 1 << 6;
+        const Break = 1 << 7;
+    }
+}
+
+bitflags::bitflags! {
+    /// Timer events that issue DMA requests
+    pub struct DmaEvent: u32 {
+        const Update = 1 << 8;
+        const C1 = 1 << 9;
+        const C2 = 1 << 10;
+        const C3 = 1 << 11;
+        const C4 = 1 << 12;
+        const Com = 1 << 13;
+        const Trigger = 1 << 14;
     }
 }
 
+/// First register of a DMA burst
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum DmaBurstBase {
+    Ctrl1 = 0,
+    Ctrl2 = 1,
+    Stctrl = 2,
+    Iden = 3,
+    Ists = 4,
+    Swevt = 5,
+    Cm1 = 6,
+    Cm2 = 7,
+    Cctrl = 8,
+    Cval = 9,
+    Div = 10,
+    Pr = 11,
+    Rpr = 12,
+    C1dt = 13,
+    C2dt = 14,
+    C3dt = 15,
+    C4dt = 16,
+    Brk = 17,
+}
+
+/// DMA burst access register (DMADT) of the timer
+///
+/// Every DMA request is served with `len` transfers to consecutive timer registers
+/// starting at the burst base register set with [`Timer::dma_burst`].
+pub struct DMAR<TMR>(PhantomData<TMR>);
+
+/// Channel data register of channel `C`, used as DMA transfer target or source
+pub struct CCR<TMR, const C: u8>(PhantomData<TMR>);
+
+impl<TMR, const C: u8> CCR<TMR, C> {
+    pub(crate) fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+unsafe impl<TMR: WithPwmCommon, const C: u8> PeriAddress for CCR<TMR, C> {
+    type MemSize = u16;
+    // Data registers of 32-bit timers must not be written with half words
+    const PERIPHERAL_WIDTH: u32 = <u32 as crate::dma::traits::Word>::WIDTH;
+
+    #[inline(always)]
+    fn address(&self) -> u32 {
+        TMR::cc_address(C)
+    }
+}
+
+unsafe impl<TMR: WithPwmCommon, const C: u8> DMASet<MemoryToPeripheral> for CCR<TMR, C> {}
+unsafe impl<TMR: WithPwmCommon, const C: u8> DMASet<PeripheralToMemory> for CCR<TMR, C> {}
+
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Error {
     /// Timer is disabled
     Disabled,
     WrongAutoReload,
+    /// Dead time is longer than the timer can generate
+    WrongDeadTime,
+    /// A previous operation is still in progress
+    Busy,
+    /// One-pulse delay is 0, so the pulse would never start
+    ZeroDelay,
 }
 
 pub trait TimerExt: Sized {
@@ -145,6 +249,17 @@ pub trait SysTimerExt: Sized {
     }
     /// Blocking [Delay] with custom precision
     fn delay(self, clocks: &Clocks) -> SysDelay;
+
+    /// Creates 64-bit uptime clock with custom precision
+    fn uptime<const FREQ: u32>(self, clocks: &Clocks) -> SysUptime<FREQ>;
+    /// Creates 64-bit uptime clock with precision of 1 ms
+    fn uptime_ms(self, clocks: &Clocks) -> SysUptimeMs {
+        self.uptime::<1_000>(clocks)
+    }
+    /// Creates 64-bit uptime clock with precision of 1 μs
+    fn uptime_us(self, clocks: &Clocks) -> SysUptimeUs {
+        self.uptime::<1_000_000>(clocks)
+    }
 }
 
 impl SysTimerExt for SYST {
@@ -157,6 +272,9 @@ impl SysTimerExt for SYST {
     fn delay(self, clocks: &Clocks) -> SysDelay {
         Timer::syst_external(self, clocks).delay()
     }
+    fn uptime<const FREQ: u32>(self, clocks: &Clocks) -> SysUptime<FREQ> {
+        Timer::syst(self, clocks).uptime()
+    }
 }
 
 /// Interrupt events
@@ -215,6 +333,32 @@ impl Timer<SYST> {
     }
 }
 
+/// Counting direction and alignment of the counter
+///
+/// In center-aligned modes the counter counts up to the auto-reload value and back down
+/// to zero, so a period lasts twice as many ticks as the auto-reload value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum CountMode {
+    /// Edge-aligned, counting up
+    Up = 0b000,
+    /// Edge-aligned, counting down
+    Down = 0b001,
+    /// Center-aligned, channel flags are set while counting down
+    CenterAligned1 = 0b010,
+    /// Center-aligned, channel flags are set while counting up
+    CenterAligned2 = 0b100,
+    /// Center-aligned, channel flags are set in both directions
+    CenterAligned3 = 0b110,
+}
+
+impl CountMode {
+    pub const fn is_center_aligned(self) -> bool {
+        self as u8 & 0b110 != 0
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
@@ -230,13 +374,17 @@ pub enum Ocm {
 }
 
 mod sealed {
-    use super::{Channel, Event, IdleState, Ocm, Polarity};
+    use super::{
+        CaptureEdge, CapturePrescaler, CaptureSource, Channel, CountMode, DmaBurstBase, DmaEvent,
+        Event, ExternalTrigger, IdleState, Ocm, Polarity, SlaveMode, TriggerSource,
+    };
     pub trait General {
         type Width: Into<u32> + From<u16>;
         fn max_auto_reload() -> u32;
         unsafe fn set_auto_reload_unchecked(&mut self, pr: u32);
         fn set_auto_reload(&mut self, pr: u32) -> Result<(), super::Error>;
         fn read_auto_reload() -> u32;
+        fn read_count_mode() -> CountMode;
         fn enable_preload(&mut self, b: bool);
         fn enable_counter(&mut self);
         fn disable_counter(&mut self);
@@ -250,10 +398,15 @@ mod sealed {
         fn get_interrupt_flag(&self) -> Event;
         fn read_count(&self) -> Self::Width;
         fn write_count(&mut self, value: Self::Width);
+        fn enable_one_pulse(&mut self, b: bool);
         fn start_one_pulse(&mut self);
         fn start_free(&mut self, update: bool);
         fn ctrl1_reset(&mut self);
         fn cnt_reset(&mut self);
+        #[cfg(feature = "async")]
+        fn waker() -> &'static crate::waker::WakerCell;
+        #[cfg(feature = "async")]
+        fn mask_pending_interrupts() -> Event;
     }
 
     pub trait WithPwmCommon: General {
@@ -261,6 +414,7 @@ mod sealed {
         const COMP_CH_NUMBER: u8;
         fn read_cc_value(channel: u8) -> u32;
         fn set_cc_value(channel: u8, value: u32);
+        fn cc_address(channel: u8) -> u32;
         fn enable_channel(channel: u8, b: bool);
         fn set_channel_polarity(channel: u8, p: Polarity);
         fn set_nchannel_polarity(channel: u8, p: Polarity);
@@ -273,11 +427,55 @@ mod sealed {
         fn idle_state(channel: u8, comp: bool, s: IdleState);
         fn enable_output(&mut self);
         fn disable_output(&mut self);
+        fn modify_brk(&mut self, mask: u32, bits: u32);
+        fn read_brk(&self) -> u32;
+        fn set_clock_division(&mut self, div: u8);
+        fn read_clock_division(&self) -> u8;
+        fn set_channel_buffer(&mut self, b: bool, on_trigger: bool);
+        fn trigger_commutation(&mut self);
+        fn set_repetition_counter(&mut self, n: u8);
     }
 
     pub trait WithPwm: WithPwmCommon {
         fn preload_output_channel_in_mode(&mut self, channel: Channel, mode: Ocm);
+        fn set_output_mode(channel: u8, mode: Ocm);
         fn start_pwm(&mut self);
+        fn enable_auto_output(&mut self);
+    }
+
+    pub trait WithPlusMode: General {
+        fn enable_plus_mode(&mut self, b: bool);
+    }
+
+    pub trait WithCountMode: General {
+        fn set_count_mode(&mut self, mode: CountMode);
+        fn is_counting_down() -> bool;
+    }
+
+    pub trait WithCapture: WithPwmCommon {
+        fn set_input_mode(channel: u8, source: CaptureSource, filter: u8, psc: CapturePrescaler);
+        fn read_input_mode(channel: u8) -> u8;
+        fn is_channel_enabled(channel: u8) -> bool;
+        fn set_capture_edge(channel: u8, edge: CaptureEdge);
+        fn is_captured(channel: u8) -> bool;
+        fn is_overcaptured(channel: u8) -> bool;
+        fn clear_capture_flags(channel: u8);
+    }
+
+    pub trait WithHall: WithCapture + WithPwm + WithSlave {
+        fn enable_hall_input(&mut self, b: bool);
+        fn trigger_output_on_c2(&mut self);
+    }
+
+    pub trait WithDmar: General {
+        fn enable_dma_request(event: DmaEvent, b: bool);
+        fn set_dma_burst(&mut self, base: DmaBurstBase, len: u8);
+    }
+
+    pub trait WithSlave: General {
+        fn set_slave_mode(&mut self, mode: SlaveMode, trigger: TriggerSource);
+        fn set_external_trigger(&mut self, config: ExternalTrigger, clock_mode_b: bool);
+        fn set_master_slave_sync(&mut self, b: bool);
     }
 
     pub trait MasterTimer: General {
@@ -285,7 +483,10 @@ mod sealed {
         fn master_mode(&mut self, mode: Self::Ptos);
     }
 }
-pub(crate) use sealed::{Advanced, General, MasterTimer, WithPwm, WithPwmCommon};
+pub(crate) use sealed::{
+    Advanced, General, MasterTimer, WithCapture, WithCountMode, WithDmar, WithHall, WithPlusMode,
+    WithPwm, WithPwmCommon, WithSlave,
+};
 
 pub trait Instance:
     crate::Sealed + crm::Enable + crm::Reset + crm::BusTimerClock + General
@@ -296,6 +497,7 @@ macro_rules! tmr {
     ($TMR:ty: [
         $Timer:ident,
         $bits:ty,
+        $(plus: $pmen:ident,)?
         $(dmar: $memsize:ty,)?
         $(c: ($cnum:tt $(, $aoe:ident)?),)?
         $(m: $tmrbase:ident,)?
@@ -308,6 +510,14 @@ macro_rules! tmr {
 
             #[inline(always)]
             fn max_auto_reload() -> u32 {
+                $(
+                    let $pmen = ();
+                    let tmr = unsafe { &*<$TMR>::ptr() };
+                    // Without PMEN (bit 10) the counter has 16 bits
+                    if tmr.ctrl1().read().bits() & (1 << 10) == 0 {
+                        return u16::MAX as u32;
+                    }
+                )?
                 <$bits>::MAX as u32
             }
             #[inline(always)]
@@ -330,6 +540,18 @@ macro_rules! tmr {
                 tmr.pr().read().bits().into()
             }
             #[inline(always)]
+            fn read_count_mode() -> CountMode {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                // CNT_DIR is bits 6:4, the direction bit is read-only in center-aligned modes
+                match (tmr.ctrl1().read().bits() >> 4) & 0b111 {
+                    0b000 => CountMode::Up,
+                    0b001 => CountMode::Down,
+                    0b010 | 0b011 => CountMode::CenterAligned1,
+                    0b100 | 0b101 => CountMode::CenterAligned2,
+                    _ => CountMode::CenterAligned3,
+                }
+            }
+            #[inline(always)]
             fn enable_preload(&mut self, b: bool) {
                 self.ctrl1().modify(|_, w| w.prben().bit(b));
             }
@@ -390,8 +612,12 @@ macro_rules! tmr {
                 self.cval().write(|w|unsafe{w.bits(value)});
             }
             #[inline(always)]
+            fn enable_one_pulse(&mut self, b: bool) {
+                self.ctrl1().modify(|_, w| w.ocmen().bit(b));
+            }
+            #[inline(always)]
             fn start_one_pulse(&mut self) {
-                self.ctrl1().modify(|_, w| unsafe { w.bits(1 << 3) }.tmren().set_bit());
+                self.ctrl1().modify(|_, w| w.ocmen().set_bit().tmren().set_bit());
             }
             #[inline(always)]
             fn start_free(&mut self, update: bool) {
@@ -405,16 +631,42 @@ macro_rules! tmr {
             fn cnt_reset(&mut self) {
                 self.cval().reset();
             }
+            #[cfg(feature = "async")]
+            fn waker() -> &'static crate::waker::WakerCell {
+                static WAKER: crate::waker::WakerCell = crate::waker::WakerCell::new();
+                &WAKER
+            }
+            #[cfg(feature = "async")]
+            fn mask_pending_interrupts() -> Event {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                cortex_m::interrupt::free(|_| {
+                    let pending = tmr.ists().read().bits() & tmr.iden().read().bits() & Event::all().bits();
+                    tmr.iden().modify(|r, w| unsafe { w.bits(r.bits() & !pending) });
+                    Event::from_bits_truncate(pending)
+                })
+            }
         }
 
 
 
         $(with_dmar!($TMR, $memsize);)?
 
+        $(
+            impl WithPlusMode for $TMR {
+                #[inline(always)]
+                fn enable_plus_mode(&mut self, b: bool) {
+                    let $pmen = ();
+                    self.ctrl1().modify(|r, w| unsafe {
+                        w.bits((r.bits() & !(1 << 10)) | ((b as u32) << 10))
+                    });
+                }
+            }
+        )?
+
         $(
             impl WithPwmCommon for $TMR {
                 const CH_NUMBER: u8 = $cnum;
-                const COMP_CH_NUMBER: u8 = $cnum;
+                const COMP_CH_NUMBER: u8 = comp_ch_number!($cnum $(, $aoe)?);
 
                 #[inline(always)]
                 fn read_cc_value(c: u8) -> u32 {
@@ -435,6 +687,12 @@ macro_rules! tmr {
                     }
                 }
 
+                #[inline(always)]
+                fn cc_address(c: u8) -> u32 {
+                    let tmr = unsafe { &*<$TMR>::ptr() };
+                    tmr.cdt(c as usize) as *const _ as u32
+                }
+
                 #[inline(always)]
                 fn enable_channel(c: u8, b: bool) {
                     let tmr = unsafe { &*<$TMR>::ptr() };
@@ -501,10 +759,52 @@ macro_rules! tmr {
                         let tmr = unsafe { &*<$TMR>::ptr() };
                         tmr.brk().modify(|_, w|  w.oen().clear_bit());
                     }
+
+                    #[inline(always)]
+                    fn modify_brk(&mut self, mask: u32, bits: u32) {
+                        self.brk().modify(|r, w| unsafe { w.bits((r.bits() & !mask) | (bits & mask)) });
+                    }
+
+                    #[inline(always)]
+                    fn read_brk(&self) -> u32 {
+                        self.brk().read().bits()
+                    }
+
+                    #[inline(always)]
+                    fn set_clock_division(&mut self, div: u8) {
+                        // CLKDIV is bits 9:8
+                        self.ctrl1().modify(|r, w| unsafe {
+                            w.bits((r.bits() & !(0b11 << 8)) | (((div & 0b11) as u32) << 8))
+                        });
+                    }
+
+                    #[inline(always)]
+                    fn read_clock_division(&self) -> u8 {
+                        ((self.ctrl1().read().bits() >> 8) & 0b11) as u8
+                    }
+
+                    #[inline(always)]
+                    fn set_channel_buffer(&mut self, b: bool, on_trigger: bool) {
+                        // CBCTRL is bit 0, CCFS bit 2
+                        let bits = (b as u32) | ((on_trigger as u32) << 2);
+                        self.ctrl2().modify(|r, w| unsafe { w.bits((r.bits() & !0b101) | bits) });
+                    }
+
+                    #[inline(always)]
+                    fn trigger_commutation(&mut self) {
+                        // HALLSWTR is bit 5
+                        self.swevt().write(|w| unsafe { w.bits(1 << 5) });
+                    }
+
+                    #[inline(always)]
+                    fn set_repetition_counter(&mut self, n: u8) {
+                        self.rpr().write(|w| unsafe { w.bits(n as _) });
+                    }
                 }
             )?
 
             with_pwm!($TMR: $cnum $(, $aoe)?);
+            with_capture!($TMR: $cnum);
         )?
 
         $(impl MasterTimer for $TMR {
@@ -518,18 +818,56 @@ macro_rules! tmr {
 }
 use tmr;
 
-// macro_rules! with_dmar {
-//     ($TMR:ty, $memsize:ty) => {
-//         unsafe impl PeriAddress for DMAR<$TMR> {
-//             #[inline(always)]
-//             fn address(&self) -> u32 {
-//                 &self.0.dmar as *const _ as u32
-//             }
+// Advanced timers have complementary outputs on channels 1-3, TMR15-17 on channel 1 only
+macro_rules! comp_ch_number {
+    ($cnum:tt) => {
+        0
+    };
+    (4, $aoe:ident) => {
+        3
+    };
+    ($cnum:tt, $aoe:ident) => {
+        1
+    };
+}
+
+macro_rules! with_dmar {
+    ($TMR:ty, $memsize:ty) => {
+        impl WithDmar for $TMR {
+            #[inline(always)]
+            fn enable_dma_request(event: DmaEvent, b: bool) {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                if b {
+                    tmr.iden()
+                        .modify(|r, w| unsafe { w.bits(r.bits() | event.bits()) });
+                } else {
+                    tmr.iden()
+                        .modify(|r, w| unsafe { w.bits(r.bits() & !event.bits()) });
+                }
+            }
+
+            #[inline(always)]
+            fn set_dma_burst(&mut self, base: DmaBurstBase, len: u8) {
+                // ADDR is bits 4:0, DTB (transfers - 1) bits 12:8
+                let bits = (base as u32) | (((len.clamp(1, 18) - 1) as u32) << 8);
+                self.dmactrl().write(|w| unsafe { w.bits(bits) });
+            }
+        }
+
+        unsafe impl PeriAddress for DMAR<$TMR> {
+            #[inline(always)]
+            fn address(&self) -> u32 {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                tmr.dmadt() as *const _ as u32
+            }
+
+            type MemSize = $memsize;
+        }
 
-//             type MemSize = $memsize;
-//         }
-//     };
-// }
+        unsafe impl DMASet<MemoryToPeripheral> for DMAR<$TMR> {}
+        unsafe impl DMASet<PeripheralToMemory> for DMAR<$TMR> {}
+    };
+}
 
 macro_rules! with_pwm {
     ($TMR:ty: [$($Cx:ident, $ccmrx_output:ident, $cxoben:ident, $cxoctrl:ident;)+] $(, $aoe:ident)?) => {
@@ -549,23 +887,39 @@ macro_rules! with_pwm {
                 }
             }
 
+            #[inline(always)]
+            #[allow(unused_unsafe)]
+            fn set_output_mode(c: u8, mode: Ocm) {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                $(
+                    if c == Channel::$Cx as u8 {
+                        tmr.$ccmrx_output().modify(|_, w| unsafe { w.$cxoctrl().bits(mode as _) });
+                    }
+                )+
+            }
+
             #[inline(always)]
             fn start_pwm(&mut self) {
-                $(let $aoe = self.brk().modify(|_, w| w.aoen().set_bit());)?
+                self.enable_auto_output();
                 self.ctrl1().modify(|_, w| w.tmren().set_bit());
             }
+
+            #[inline(always)]
+            fn enable_auto_output(&mut self) {
+                $(let $aoe = self.brk().modify(|_, w| w.aoen().set_bit());)?
+            }
         }
     };
-    ($TMR:ty: 1) => {
+    ($TMR:ty: 1 $(, $aoe:ident)?) => {
         with_pwm!($TMR: [
             C1, cm1_output, c1oben, c1octrl;
-        ]);
+        ] $(, $aoe)?);
     };
-    ($TMR:ty: 2) => {
+    ($TMR:ty: 2 $(, $aoe:ident)?) => {
         with_pwm!($TMR: [
             C1, cm1_output, c1oben, c1octrl;
             C2, cm1_output, c2oben, c2octrl;
-        ]);
+        ] $(, $aoe)?);
     };
     ($TMR:ty: 4 $(, $aoe:ident)?) => {
         with_pwm!($TMR: [
@@ -574,6 +928,174 @@ macro_rules! with_pwm {
             C3, cm2_output, c3oben, c3octrl;
             C4, cm2_output, c4oben, c4octrl;
         ] $(, $aoe)?);
+        with_count_mode!($TMR);
+    };
+}
+
+macro_rules! with_count_mode {
+    ($TMR:ty) => {
+        impl WithCountMode for $TMR {
+            #[inline(always)]
+            fn set_count_mode(&mut self, mode: CountMode) {
+                // CNT_DIR is bits 6:4
+                self.ctrl1().modify(|r, w| unsafe {
+                    w.bits((r.bits() & !(0b111 << 4)) | ((mode as u32) << 4))
+                });
+            }
+
+            #[inline(always)]
+            fn is_counting_down() -> bool {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                tmr.ctrl1().read().bits() & (1 << 4) != 0
+            }
+        }
+    };
+}
+
+macro_rules! with_capture {
+    ($TMR:ty: [$($Cx:ident, $ccmrx_input:ident, $offset:literal;)+]) => {
+        impl WithCapture for $TMR {
+            #[inline(always)]
+            fn set_input_mode(c: u8, source: CaptureSource, filter: u8, psc: CapturePrescaler) {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                // CxC selects the input, CxIDIV is the prescaler, CxDF the digital filter
+                let bits = (source as u32) | ((psc as u32) << 2) | (((filter & 0xf) as u32) << 4);
+                $(
+                    if c == Channel::$Cx as u8 {
+                        tmr.$ccmrx_input().modify(|r, w| unsafe {
+                            w.bits((r.bits() & !(0xff << $offset)) | (bits << $offset))
+                        });
+                    }
+                )+
+            }
+
+            #[inline(always)]
+            fn read_input_mode(c: u8) -> u8 {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                $(
+                    if c == Channel::$Cx as u8 {
+                        return (tmr.$ccmrx_input().read().bits() >> $offset) as u8;
+                    }
+                )+
+                0
+            }
+
+            #[inline(always)]
+            fn is_channel_enabled(c: u8) -> bool {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                c < Self::CH_NUMBER && tmr.cctrl().read().bits() & (1 << (c * 4)) != 0
+            }
+
+            #[inline(always)]
+            fn set_capture_edge(c: u8, edge: CaptureEdge) {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                if c < Self::CH_NUMBER {
+                    let (cp, ccp) = match edge {
+                        CaptureEdge::Rising => (false, false),
+                        CaptureEdge::Falling => (true, false),
+                        CaptureEdge::Both => (true, true),
+                    };
+                    unsafe {
+                        bb::write(&tmr.cctrl(), c*4 + 1, cp);
+                        bb::write(&tmr.cctrl(), c*4 + 3, ccp);
+                    }
+                }
+            }
+
+            #[inline(always)]
+            fn is_captured(c: u8) -> bool {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                c < Self::CH_NUMBER && tmr.ists().read().bits() & (1 << (c + 1)) != 0
+            }
+
+            #[inline(always)]
+            fn is_overcaptured(c: u8) -> bool {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                c < Self::CH_NUMBER && tmr.ists().read().bits() & (1 << (c + 9)) != 0
+            }
+
+            #[inline(always)]
+            fn clear_capture_flags(c: u8) {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                if c < Self::CH_NUMBER {
+                    tmr.ists().write(|w| unsafe { w.bits(0xffff & !((1 << (c + 1)) | (1 << (c + 9)))) });
+                }
+            }
+        }
+    };
+    ($TMR:ty: 1) => {
+        with_capture!($TMR: [
+            C1, cm1_input, 0;
+        ]);
+    };
+    ($TMR:ty: 2) => {
+        with_capture!($TMR: [
+            C1, cm1_input, 0;
+            C2, cm1_input, 8;
+        ]);
+        with_slave!($TMR);
+    };
+    ($TMR:ty: 4) => {
+        with_capture!($TMR: [
+            C1, cm1_input, 0;
+            C2, cm1_input, 8;
+            C3, cm2_input, 0;
+            C4, cm2_input, 8;
+        ]);
+        with_slave!($TMR);
+        with_hall!($TMR);
+    };
+}
+
+macro_rules! with_hall {
+    ($TMR:ty) => {
+        impl WithHall for $TMR {
+            #[inline(always)]
+            fn enable_hall_input(&mut self, b: bool) {
+                // C1INSEL is bit 7, TI1 becomes the XOR of the channel 1, 2 and 3 inputs
+                self.ctrl2()
+                    .modify(|r, w| unsafe { w.bits((r.bits() & !(1 << 7)) | ((b as u32) << 7)) });
+            }
+
+            #[inline(always)]
+            fn trigger_output_on_c2(&mut self) {
+                // PTOS is bits 6:4, 0b101 outputs the channel 2 compare reference
+                self.ctrl2()
+                    .modify(|r, w| unsafe { w.bits((r.bits() & !(0b111 << 4)) | (0b101 << 4)) });
+            }
+        }
+    };
+}
+
+macro_rules! with_slave {
+    ($TMR:ty) => {
+        impl WithSlave for $TMR {
+            #[inline(always)]
+            fn set_slave_mode(&mut self, mode: SlaveMode, trigger: TriggerSource) {
+                // SMSEL is bits 2:0, STIS bits 6:4
+                let bits = (mode as u32) | ((trigger as u32) << 4);
+                self.stctrl()
+                    .modify(|r, w| unsafe { w.bits((r.bits() & !0x77) | bits) });
+            }
+
+            #[inline(always)]
+            fn set_external_trigger(&mut self, config: ExternalTrigger, clock_mode_b: bool) {
+                // ESF is bits 11:8, ESDIV bits 13:12, ECMBEN bit 14, ESP bit 15
+                let bits = (((config.filter & 0xf) as u32) << 8)
+                    | ((config.prescaler as u32) << 12)
+                    | ((clock_mode_b as u32) << 14)
+                    | ((config.inverted as u32) << 15);
+                self.stctrl()
+                    .modify(|r, w| unsafe { w.bits((r.bits() & !0xff00) | bits) });
+            }
+
+            #[inline(always)]
+            fn set_master_slave_sync(&mut self, b: bool) {
+                // STS is bit 7
+                self.stctrl()
+                    .modify(|r, w| unsafe { w.bits((r.bits() & !(1 << 7)) | ((b as u32) << 7)) });
+            }
+        }
     };
 }
 
@@ -626,12 +1148,41 @@ impl<TMR: Instance> Timer<TMR> {
     }
 }
 
+impl<TMR: Instance + WithDmar> Timer<TMR> {
+    /// Issue DMA requests on `event`
+    pub fn listen_dma(&mut self, event: DmaEvent) {
+        TMR::enable_dma_request(event, true);
+    }
+
+    /// Stop issuing DMA requests on `event`
+    pub fn unlisten_dma(&mut self, event: DmaEvent) {
+        TMR::enable_dma_request(event, false);
+    }
+
+    /// Configure DMA burst mode, so that every DMA request transfers `len` registers
+    /// starting at `base` through the returned [`DMAR`]
+    pub fn dma_burst(&mut self, base: DmaBurstBase, len: u8) -> DMAR<TMR> {
+        self.tmr.set_dma_burst(base, len);
+        DMAR(PhantomData)
+    }
+}
+
 impl<TMR: Instance + MasterTimer> Timer<TMR> {
     pub fn set_master_mode(&mut self, mode: TMR::Ptos) {
         self.tmr.master_mode(mode)
     }
 }
 
+impl<TMR: Instance + WithPlusMode> Timer<TMR> {
+    /// Count with 32 bits instead of 16 bits (plus mode)
+    ///
+    /// The counter is stopped, the auto-reload value must be set again afterwards.
+    pub fn enable_32bit(&mut self, b: bool) {
+        self.tmr.disable_counter();
+        self.tmr.enable_plus_mode(b);
+    }
+}
+
 /// Timer wrapper for fixed precision timers.
 ///
 /// Uses `fugit::TimerDurationU32` for most of operations
@@ -710,12 +1261,42 @@ impl<TMR: Instance, const FREQ: u32> FTimer<TMR, FREQ> {
     }
 }
 
+impl<TMR: Instance + WithDmar, const FREQ: u32> FTimer<TMR, FREQ> {
+    /// Issue DMA requests on `event`
+    pub fn listen_dma(&mut self, event: DmaEvent) {
+        TMR::enable_dma_request(event, true);
+    }
+
+    /// Stop issuing DMA requests on `event`
+    pub fn unlisten_dma(&mut self, event: DmaEvent) {
+        TMR::enable_dma_request(event, false);
+    }
+
+    /// Configure DMA burst mode, so that every DMA request transfers `len` registers
+    /// starting at `base` through the returned [`DMAR`]
+    pub fn dma_burst(&mut self, base: DmaBurstBase, len: u8) -> DMAR<TMR> {
+        self.tmr.set_dma_burst(base, len);
+        DMAR(PhantomData)
+    }
+}
+
 impl<TMR: Instance + MasterTimer, const FREQ: u32> FTimer<TMR, FREQ> {
     pub fn set_master_mode(&mut self, mode: TMR::Ptos) {
         self.tmr.master_mode(mode)
     }
 }
 
+impl<TMR: Instance + WithPlusMode, const FREQ: u32> FTimer<TMR, FREQ> {
+    /// Count with 32 bits instead of 16 bits (plus mode)
+    ///
+    /// Call this before creating a [`Counter`] or [`Delay`] to use the full 32-bit range.
+    /// The counter is stopped.
+    pub fn enable_32bit(&mut self, b: bool) {
+        self.tmr.disable_counter();
+        self.tmr.enable_plus_mode(b);
+    }
+}
+
 #[inline(always)]
 pub(crate) const fn compute_arr_presc(freq: u32, clock: u32) -> (u16, u32) {
     let ticks = clock / freq;
@@ -725,55 +1306,62 @@ pub(crate) const fn compute_arr_presc(freq: u32, clock: u32) -> (u16, u32) {
 }
 
 #[cfg(feature = "tmr1")]
-tmr!(pac::TMR1: [Timer1, u16, c: (4, _aoe), m: tmr1,]);
+tmr!(pac::TMR1: [Timer1, u16, dmar: u16, c: (4, _aoe), m: tmr1,]);
+
+// TMR2 and TMR5 count with 16 bits unless PMEN (CTRL1 bit 10) is set, except TMR2 of
+// AT32F425 which always has 32 bits and no PMEN
+#[cfg(feature = "tmr2")]
+#[cfg(not(feature = "at32f425"))]
+tmr!(pac::TMR2: [Timer2, u32, plus: _pmen, dmar: u32, c: (4), m: tmr2,]);
 
 #[cfg(feature = "tmr2")]
-tmr!(pac::TMR2: [Timer2, u32, c: (4), m: tmr2,]);
+#[cfg(feature = "at32f425")]
+tmr!(pac::TMR2: [Timer2, u32, dmar: u32, c: (4), m: tmr2,]);
 
 #[cfg(feature = "tmr3")]
-tmr!(pac::TMR3: [Timer3, u16, c: (4), m: tmr3,]);
+tmr!(pac::TMR3: [Timer3, u16, dmar: u16, c: (4), m: tmr3,]);
 
 #[cfg(feature = "tmr4")]
-tmr!(pac::TMR4: [Timer4, u16, c: (4), m: tmr4,]);
+tmr!(pac::TMR4: [Timer4, u16, dmar: u16, c: (4), m: tmr4,]);
 
 #[cfg(feature = "tmr5")]
-tmr!(pac::TMR5: [Timer5, u32, c: (4), m: tmr5,]);
+tmr!(pac::TMR5: [Timer5, u32, plus: _pmen, dmar: u32, c: (4), m: tmr5,]);
 
 #[cfg(feature = "tmr6")]
-tmr!(pac::TMR6: [Timer6, u16, c: (4), m: tmr6,]);
+tmr!(pac::TMR6: [Timer6, u16, m: tmr6,]);
 
 #[cfg(feature = "tmr7")]
-tmr!(pac::TMR7: [Timer7, u16, c: (4), m: tmr7,]);
+tmr!(pac::TMR7: [Timer7, u16, m: tmr7,]);
 
 #[cfg(feature = "tmr8")]
-tmr!(pac::TMR8: [Timer8, u16, c: (4), m: tmr8,]);
+tmr!(pac::TMR8: [Timer8, u16, dmar: u16, c: (4, _aoe), m: tmr8,]);
 
 #[cfg(feature = "tmr9")]
 tmr!(pac::TMR9: [Timer9, u16, c: (2),]);
 
-// #[cfg(feature = "tmr10")]
-// tmr!(pac::TMR10: [Timer10, u16, c: (4), m: tmr10,]);
+#[cfg(feature = "tmr10")]
+tmr!(pac::TMR10: [Timer10, u16, c: (1),]);
 
-// #[cfg(feature = "tmr11")]
-// tmr!(pac::TMR11: [Timer11, u16, c: (4), m: tmr11,]);
+#[cfg(feature = "tmr11")]
+tmr!(pac::TMR11: [Timer11, u16, c: (1),]);
 
 #[cfg(feature = "tmr12")]
-tmr!(pac::TMR12: [Timer12, u16, c: (4),]);
+tmr!(pac::TMR12: [Timer12, u16, c: (2),]);
 
 #[cfg(feature = "tmr13")]
-tmr!(pac::TMR13: [Timer13, u16, c: (4), m:]);
+tmr!(pac::TMR13: [Timer13, u16, c: (1),]);
 
 #[cfg(feature = "tmr14")]
-tmr!(pac::TMR14: [Timer14, u16, c: (4),]);
+tmr!(pac::TMR14: [Timer14, u16, c: (1),]);
 
 #[cfg(feature = "tmr15")]
-tmr!(pac::TMR15: [Timer15, u16, c: (4), m: tmr15,]);
+tmr!(pac::TMR15: [Timer15, u16, dmar: u16, c: (2, _aoe), m: tmr15,]);
 
 #[cfg(feature = "tmr16")]
-tmr!(pac::TMR16: [Timer16, u16, c: (4), m: tmr16,]);
+tmr!(pac::TMR16: [Timer16, u16, dmar: u16, c: (1, _aoe),]);
 
 #[cfg(feature = "tmr17")]
-tmr!(pac::TMR17: [Timer17, u16, c: (4), m: tmr17,]);
+tmr!(pac::TMR17: [Timer17, u16, dmar: u16, c: (1, _aoe),]);
 
 #[cfg(feature = "tmr20")]
-tmr!(pac::TMR20: [Timer20, u16, c: (4), m: tmr20,]);
+tmr!(pac::TMR20: [Timer20, u16, dmar: u16, c: (4, _aoe), m: tmr20,]);
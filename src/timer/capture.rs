@@ -0,0 +1,440 @@
+//! Input capture and PWM input measurement
+//!
+//! [`Capture`] runs the timer as a free counter with the fixed `FREQ` tick rate and latches
+//! the counter value into the channel data register on the selected edge of the channel input.
+//! Channels are picked with [`CaptureInput`]s, which put the pin into the input mode of its
+//! alternate function. A pull resistor configured on an input pin before is kept, and the
+//! pin is given back by [`CaptureChannel::release`] and [`CaptureInput::release`]:
+//!
+//! ```rust
+//! let mut capture = dp.TMR3.capture_us(&clocks);
+//! let pa6 = gpioa.pa6.into_pull_up_input();
+//! let mut tach = capture.channel(CaptureInput1::new(pa6), CaptureEdge::Rising);
+//! tach.set_filter(0b0011);
+//! // in the TMR3 interrupt or a polling loop
+//! if tach.is_captured() {
+//!     let ticks = tach.get_capture();
+//! }
+//! let pa6 = tach.release().release();
+//! ```
+//!
+//! [`PwmInput`] uses channels 1 and 2 on the same input pin together with the slave reset mode,
+//! so the period and the pulse width of an external PWM signal are measured by hardware.
+//!
+//! ```rust
+//! let pwm_in = dp.TMR3.pwm_input_us(gpioa.pa6, &clocks);
+//! if let Some(period) = pwm_in.get_period() {
+//!     let duty = pwm_in.get_duty();
+//! }
+//! ```
+
+use super::{
+    CPin, Channel, Event, FTimer, Instance, SlaveMode, TriggerSource, WithCapture, WithSlave,
+};
+use super::{C1, C2, C3, C4};
+use crate::crm::Clocks;
+use crate::gpio::Input;
+use core::ops::{Deref, DerefMut};
+use fugit::{HertzU32 as Hertz, TimerDurationU32};
+
+/// Edge of the channel input that triggers a capture
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CaptureEdge {
+    Rising,
+    Falling,
+    Both,
+}
+
+/// Number of input events needed for one capture
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum CapturePrescaler {
+    Div1 = 0,
+    Div2 = 1,
+    Div4 = 2,
+    Div8 = 3,
+}
+
+pub type CaptureInput1<TMR> = CaptureInput<TMR, C1>;
+pub type CaptureInput2<TMR> = CaptureInput<TMR, C2>;
+pub type CaptureInput3<TMR> = CaptureInput<TMR, C3>;
+pub type CaptureInput4<TMR> = CaptureInput<TMR, C4>;
+
+/// Input pin of channel `C`
+///
+/// The pin is switched to the input mode of its alternate function, `TMR::Ch<Input>`,
+/// instead of the push-pull output used by PWM channels.
+pub struct CaptureInput<TMR: CPin<C>, const C: u8> {
+    pin: TMR::Ch<Input>,
+}
+
+impl<TMR, const C: u8> CaptureInput<TMR, C>
+where
+    TMR: CPin<C>,
+{
+    pub fn new(pin: impl Into<TMR::Ch<Input>>) -> Self {
+        Self { pin: pin.into() }
+    }
+
+    /// Get the pin back, e.g. to convert it into another mode with `TryFrom`
+    pub fn release(self) -> TMR::Ch<Input> {
+        self.pin
+    }
+}
+
+/// Input that feeds the capture channel
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CaptureSource {
+    /// Input of the same channel (C1 from TI1, C2 from TI2, ...)
+    Direct = 0b01,
+    /// Input of the paired channel (C1 from TI2, C2 from TI1, ...)
+    Indirect = 0b10,
+    /// Trigger input selected in the slave mode controller
+    Trc = 0b11,
+}
+
+pub trait CaptureExt
+where
+    Self: Sized + Instance + WithCapture,
+{
+    fn capture<const FREQ: u32>(self, clocks: &Clocks) -> Capture<Self, FREQ>;
+
+    fn capture_us(self, clocks: &Clocks) -> Capture<Self, 1_000_000> {
+        self.capture::<1_000_000>(clocks)
+    }
+
+    fn pwm_input<const FREQ: u32>(
+        self,
+        pin: impl Into<<Self as CPin<C1>>::Ch<Input>>,
+        clocks: &Clocks,
+    ) -> PwmInput<Self, FREQ>
+    where
+        Self: WithSlave + CPin<C1>;
+
+    fn pwm_input_us(
+        self,
+        pin: impl Into<<Self as CPin<C1>>::Ch<Input>>,
+        clocks: &Clocks,
+    ) -> PwmInput<Self, 1_000_000>
+    where
+        Self: WithSlave + CPin<C1>,
+    {
+        self.pwm_input::<1_000_000>(pin, clocks)
+    }
+}
+
+impl<TMR> CaptureExt for TMR
+where
+    Self: Sized + Instance + WithCapture,
+{
+    fn capture<const FREQ: u32>(self, clocks: &Clocks) -> Capture<Self, FREQ> {
+        FTimer::<Self, FREQ>::new(self, clocks).capture()
+    }
+
+    fn pwm_input<const FREQ: u32>(
+        self,
+        pin: impl Into<<Self as CPin<C1>>::Ch<Input>>,
+        clocks: &Clocks,
+    ) -> PwmInput<Self, FREQ>
+    where
+        Self: WithSlave + CPin<C1>,
+    {
+        FTimer::<Self, FREQ>::new(self, clocks).pwm_input(pin)
+    }
+}
+
+pub struct Capture<TMR, const FREQ: u32>
+where
+    TMR: Instance + WithCapture,
+{
+    timer: FTimer<TMR, FREQ>,
+}
+
+impl<TMR: Instance + WithCapture, const FREQ: u32> FTimer<TMR, FREQ> {
+    pub fn capture(mut self) -> Capture<TMR, FREQ> {
+        // Let the counter wrap at its full range, so differences of captures are valid
+        self.tmr.set_auto_reload(TMR::max_auto_reload()).unwrap();
+        self.tmr.trigger_update();
+        self.tmr.enable_counter();
+
+        Capture { timer: self }
+    }
+}
+
+impl<TMR, const FREQ: u32> Capture<TMR, FREQ>
+where
+    TMR: Instance + WithCapture,
+{
+    /// Configure channel `C` for input capture on `edge` and enable it
+    pub fn channel<const C: u8>(
+        &mut self,
+        input: CaptureInput<TMR, C>,
+        edge: CaptureEdge,
+    ) -> CaptureChannel<TMR, C>
+    where
+        TMR: CPin<C>,
+    {
+        TMR::enable_channel(C, false);
+        TMR::set_input_mode(C, CaptureSource::Direct, 0, CapturePrescaler::Div1);
+        TMR::set_capture_edge(C, edge);
+        TMR::clear_capture_flags(C);
+        TMR::enable_channel(C, true);
+        CaptureChannel { input }
+    }
+
+    /// Number of ticks after which the counter wraps
+    pub fn get_max_ticks(&self) -> u32 {
+        TMR::read_auto_reload()
+    }
+
+    pub fn release(mut self) -> FTimer<TMR, FREQ> {
+        // stop counter
+        self.tmr.ctrl1_reset();
+        self.timer
+    }
+}
+
+impl<TMR, const FREQ: u32> Deref for Capture<TMR, FREQ>
+where
+    TMR: Instance + WithCapture,
+{
+    type Target = FTimer<TMR, FREQ>;
+    fn deref(&self) -> &Self::Target {
+        &self.timer
+    }
+}
+
+impl<TMR, const FREQ: u32> DerefMut for Capture<TMR, FREQ>
+where
+    TMR: Instance + WithCapture,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.timer
+    }
+}
+
+/// Single input capture channel
+pub struct CaptureChannel<TMR: CPin<C>, const C: u8> {
+    input: CaptureInput<TMR, C>,
+}
+
+impl<TMR: Instance + WithCapture + CPin<C>, const C: u8> CaptureChannel<TMR, C> {
+    /// Disable capture on the channel
+    #[inline]
+    pub fn disable(&mut self) {
+        TMR::enable_channel(C, false);
+    }
+
+    /// Enable capture on the channel
+    #[inline]
+    pub fn enable(&mut self) {
+        TMR::enable_channel(C, true);
+    }
+
+    /// Select the input edge that triggers a capture
+    #[inline]
+    pub fn set_edge(&mut self, edge: CaptureEdge) {
+        TMR::set_capture_edge(C, edge);
+    }
+
+    /// Set the digital input filter (`0` disables filtering, `0b1111` is the strongest)
+    #[inline]
+    pub fn set_filter(&mut self, filter: u8) {
+        self.set_input(filter, self.prescaler());
+    }
+
+    /// Capture once every 1, 2, 4 or 8 input events
+    #[inline]
+    pub fn set_prescaler(&mut self, psc: CapturePrescaler) {
+        self.set_input(self.filter(), psc);
+    }
+
+    /// Get the last captured counter value
+    ///
+    /// Reading the value clears the capture flag
+    #[inline]
+    pub fn get_capture(&self) -> u32 {
+        TMR::read_cc_value(C)
+    }
+
+    /// Returns `true` if a new value was captured since the last read
+    #[inline]
+    pub fn is_captured(&self) -> bool {
+        TMR::is_captured(C)
+    }
+
+    /// Returns `true` if a capture happened while the previous value was still unread
+    #[inline]
+    pub fn is_overcaptured(&self) -> bool {
+        TMR::is_overcaptured(C)
+    }
+
+    /// Clear capture and overcapture flags of the channel
+    #[inline]
+    pub fn clear_overcapture(&mut self) {
+        TMR::clear_capture_flags(C);
+    }
+
+    /// Capture/compare event of this channel
+    pub const fn event(&self) -> Event {
+        match C {
+            0 => Event::C1,
+            1 => Event::C2,
+            2 => Event::C3,
+            _ => Event::C4,
+        }
+    }
+
+    /// Disable capture on the channel and release its input
+    pub fn release(self) -> CaptureInput<TMR, C> {
+        TMR::enable_channel(C, false);
+        self.input
+    }
+
+    fn set_input(&mut self, filter: u8, psc: CapturePrescaler) {
+        // Input configuration can only be changed while the channel is disabled
+        let enabled = self.is_enabled();
+        TMR::enable_channel(C, false);
+        TMR::set_input_mode(C, CaptureSource::Direct, filter, psc);
+        TMR::enable_channel(C, enabled);
+    }
+
+    fn is_enabled(&self) -> bool {
+        TMR::is_channel_enabled(C)
+    }
+
+    fn filter(&self) -> u8 {
+        TMR::read_input_mode(C) >> 4
+    }
+
+    fn prescaler(&self) -> CapturePrescaler {
+        match (TMR::read_input_mode(C) >> 2) & 0b11 {
+            0 => CapturePrescaler::Div1,
+            1 => CapturePrescaler::Div2,
+            2 => CapturePrescaler::Div4,
+            _ => CapturePrescaler::Div8,
+        }
+    }
+}
+
+/// PWM input measurement on channel 1 input
+///
+/// Channel 1 captures the period on the rising edge, which also resets the counter,
+/// channel 2 captures the pulse width on the falling edge.
+pub struct PwmInput<TMR, const FREQ: u32>
+where
+    TMR: Instance + WithCapture + WithSlave + CPin<C1>,
+{
+    timer: FTimer<TMR, FREQ>,
+    pin: TMR::Ch<Input>,
+}
+
+impl<TMR: Instance + WithCapture + WithSlave + CPin<C1>, const FREQ: u32> FTimer<TMR, FREQ> {
+    pub fn pwm_input(mut self, pin: impl Into<TMR::Ch<Input>>) -> PwmInput<TMR, FREQ> {
+        let pin = pin.into();
+
+        self.tmr.set_auto_reload(TMR::max_auto_reload()).unwrap();
+
+        let (c1, c2) = (Channel::C1 as u8, Channel::C2 as u8);
+        TMR::set_input_mode(c1, CaptureSource::Direct, 0, CapturePrescaler::Div1);
+        TMR::set_capture_edge(c1, CaptureEdge::Rising);
+        TMR::set_input_mode(c2, CaptureSource::Indirect, 0, CapturePrescaler::Div1);
+        TMR::set_capture_edge(c2, CaptureEdge::Falling);
+        self.tmr
+            .set_slave_mode(SlaveMode::Reset, TriggerSource::Ti1);
+
+        TMR::enable_channel(c1, true);
+        TMR::enable_channel(c2, true);
+
+        self.tmr.trigger_update();
+        self.tmr.enable_counter();
+
+        PwmInput { timer: self, pin }
+    }
+}
+
+impl<TMR, const FREQ: u32> PwmInput<TMR, FREQ>
+where
+    TMR: Instance + WithCapture + WithSlave + CPin<C1>,
+{
+    /// Set the digital input filter of the measured signal
+    pub fn set_filter(&mut self, filter: u8) {
+        let (c1, c2) = (Channel::C1 as u8, Channel::C2 as u8);
+        TMR::enable_channel(c1, false);
+        TMR::enable_channel(c2, false);
+        TMR::set_input_mode(c1, CaptureSource::Direct, filter, CapturePrescaler::Div1);
+        TMR::set_input_mode(c2, CaptureSource::Indirect, filter, CapturePrescaler::Div1);
+        TMR::enable_channel(c1, true);
+        TMR::enable_channel(c2, true);
+    }
+
+    /// Get the period of the input signal
+    ///
+    /// Returns `None` until the first full period has been measured
+    pub fn get_period(&self) -> Option<TimerDurationU32<FREQ>> {
+        match TMR::read_cc_value(Channel::C1 as u8) {
+            0 => None,
+            ticks => Some(TimerDurationU32::from_ticks(ticks)),
+        }
+    }
+
+    /// Get the high time of the input signal
+    pub fn get_duty(&self) -> TimerDurationU32<FREQ> {
+        TimerDurationU32::from_ticks(TMR::read_cc_value(Channel::C2 as u8))
+    }
+
+    /// Get the frequency of the input signal
+    pub fn get_frequency(&self) -> Option<Hertz> {
+        self.get_period()
+            .map(|period| Hertz::from_raw(FREQ / period.ticks()))
+    }
+
+    /// Get the duty cycle in 1/65536 units of the period
+    pub fn get_duty_cycle(&self) -> Option<u16> {
+        self.get_period().map(|period| {
+            let duty = self.get_duty().ticks() as u64;
+            ((duty << 16) / period.ticks() as u64).min(u16::MAX as u64) as u16
+        })
+    }
+
+    /// Returns `true` if a capture was lost because the previous one was not read in time
+    pub fn is_overcaptured(&self) -> bool {
+        TMR::is_overcaptured(Channel::C1 as u8) || TMR::is_overcaptured(Channel::C2 as u8)
+    }
+
+    /// Clear overcapture flags of both channels
+    pub fn clear_overcapture(&mut self) {
+        TMR::clear_capture_flags(Channel::C1 as u8);
+        TMR::clear_capture_flags(Channel::C2 as u8);
+    }
+
+    /// Stop the measurement and release the timer and the input pin
+    pub fn release(mut self) -> (FTimer<TMR, FREQ>, TMR::Ch<Input>) {
+        // stop counter
+        self.tmr.ctrl1_reset();
+        (self.timer, self.pin)
+    }
+}
+
+impl<TMR, const FREQ: u32> Deref for PwmInput<TMR, FREQ>
+where
+    TMR: Instance + WithCapture + WithSlave + CPin<C1>,
+{
+    type Target = FTimer<TMR, FREQ>;
+    fn deref(&self) -> &Self::Target {
+        &self.timer
+    }
+}
+
+impl<TMR, const FREQ: u32> DerefMut for PwmInput<TMR, FREQ>
+where
+    TMR: Instance + WithCapture + WithSlave + CPin<C1>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.timer
+    }
+}
@@ -0,0 +1,195 @@
+//! Edge timestamps captured by DMA
+//!
+//! [`CaptureStream`] copies every captured counter value of a channel into a circular
+//! buffer by DMA, so no interrupt is needed per edge. The stream yields the times between
+//! consecutive edges, e.g. to decode IR remote, DShot telemetry or 1-wire-like protocols:
+//!
+//! ```rust
+//! static mut BUF: [u16; 64] = [0; 64];
+//!
+//! let dma1 = dp.DMA1.split();
+//! let mut capture = dp.TMR3.capture_us(&clocks);
+//! let mut ir = capture.stream(
+//!     CaptureInput1::new(gpioa.pa6),
+//!     CaptureEdge::Both,
+//!     dma1.6,
+//!     unsafe { &mut *core::ptr::addr_of_mut!(BUF) },
+//! );
+//! ir.start();
+//! for pulse in ir.deltas() {
+//!     decoder.feed(pulse);
+//! }
+//! ```
+//!
+//! The buffer must be read before the DMA wraps around and overwrites unread values,
+//! e.g. on the half transfer and transfer complete interrupts of the DMA channel.
+//! Timers in 32-bit plus mode are not supported, the values are stored as 16-bit words.
+
+use super::{
+    CPin, Capture, CaptureChannel, CaptureEdge, CaptureInput, DmaEvent, Instance, WithCapture,
+    WithDmar, CCR,
+};
+use crate::dma::{self, PeripheralToMemory, Transfer};
+use core::sync::atomic::{compiler_fence, Ordering};
+use fugit::TimerDurationU32;
+
+type StreamTransfer<TMR, DMA, const C: u8, const N: u8> =
+    Transfer<dma::Channel<DMA, N>, CCR<TMR, C>, &'static mut [u16], PeripheralToMemory>;
+
+/// Circular DMA stream of the captures of channel `C`
+pub struct CaptureStream<TMR, DMA, const C: u8, const N: u8, const FREQ: u32>
+where
+    TMR: Instance + WithCapture + WithDmar + CPin<C>,
+    DMA: dma::traits::Instance,
+{
+    channel: CaptureChannel<TMR, C>,
+    transfer: StreamTransfer<TMR, DMA, C, N>,
+    len: usize,
+    read: usize,
+    last: Option<u16>,
+}
+
+impl<TMR, const FREQ: u32> Capture<TMR, FREQ>
+where
+    TMR: Instance + WithCapture + WithDmar,
+{
+    /// Capture `edge` of channel `C` into `buf` by DMA
+    ///
+    /// The returned stream must be started. On devices with fixed DMA mapping `dma_channel`
+    /// must be the channel of the capture request of `C`.
+    pub fn stream<const C: u8, DMA, const N: u8>(
+        &mut self,
+        input: CaptureInput<TMR, C>,
+        edge: CaptureEdge,
+        dma_channel: dma::Channel<DMA, N>,
+        buf: &'static mut [u16],
+    ) -> CaptureStream<TMR, DMA, C, N, FREQ>
+    where
+        TMR: CPin<C>,
+        DMA: dma::traits::Instance,
+    {
+        assert!(TMR::read_auto_reload() == u16::MAX as u32);
+        assert!(!buf.is_empty());
+        let channel = self.channel(input, edge);
+
+        let len = buf.len();
+        CaptureStream {
+            channel,
+            transfer: Transfer::init_peripheral_to_memory(dma_channel, CCR::new(), buf, true),
+            len,
+            read: 0,
+            last: None,
+        }
+    }
+}
+
+impl<TMR, DMA, const C: u8, const N: u8, const FREQ: u32> CaptureStream<TMR, DMA, C, N, FREQ>
+where
+    TMR: Instance + WithCapture + WithDmar + CPin<C>,
+    DMA: dma::traits::Instance,
+{
+    /// Start copying captures into the buffer
+    pub fn start(&mut self) {
+        TMR::clear_capture_flags(C);
+        self.transfer.start();
+        TMR::enable_dma_request(dma_event(C), true);
+    }
+
+    /// Stop copying captures, the unread ones stay available
+    pub fn pause(&mut self) {
+        TMR::enable_dma_request(dma_event(C), false);
+    }
+
+    /// Number of captures written by the DMA and not read yet
+    pub fn pending(&self) -> usize {
+        (self.write_index() + self.len - self.read) % self.len
+    }
+
+    /// Get the time between the next two captured edges
+    ///
+    /// Returns `None` if no new capture is available. The first capture after
+    /// [`start`](Self::start) or [`reset`](Self::reset) only serves as reference.
+    pub fn next_delta(&mut self) -> Option<TimerDurationU32<FREQ>> {
+        loop {
+            let value = self.next_value()?;
+            if let Some(last) = self.last.replace(value) {
+                return Some(TimerDurationU32::from_ticks(value.wrapping_sub(last) as u32));
+            }
+        }
+    }
+
+    /// Iterate over the times between the captured edges available now
+    pub fn deltas(&mut self) -> impl Iterator<Item = TimerDurationU32<FREQ>> + '_ {
+        core::iter::from_fn(move || self.next_delta())
+    }
+
+    /// Drop all unread captures, the next one becomes the new reference
+    pub fn reset(&mut self) {
+        self.read = self.write_index();
+        self.last = None;
+    }
+
+    /// Returns `true` once half of the buffer has been written
+    pub fn is_half_complete(&self) -> bool {
+        self.transfer.is_half_complete()
+    }
+
+    /// Returns `true` once the end of the buffer has been written
+    pub fn is_complete(&self) -> bool {
+        self.transfer.is_complete()
+    }
+
+    /// Clear the flags of `event`, usually from the DMA channel interrupt
+    pub fn clear_events(&mut self, event: dma::Event) {
+        self.transfer.clear_events(event);
+    }
+
+    /// Enable DMA interrupts for `event`
+    pub fn listen(&mut self, event: dma::Event) {
+        self.transfer.listen(event);
+    }
+
+    /// Disable DMA interrupts for `event`
+    pub fn unlisten(&mut self, event: dma::Event) {
+        self.transfer.unlisten(event);
+    }
+
+    /// Stop the stream and release the capture input, the DMA channel and the buffer
+    #[allow(clippy::type_complexity)]
+    pub fn release(
+        self,
+    ) -> (
+        CaptureInput<TMR, C>,
+        dma::Channel<DMA, N>,
+        &'static mut [u16],
+    ) {
+        TMR::enable_dma_request(dma_event(C), false);
+        let (channel, _, buf) = self.transfer.release();
+        (self.channel.release(), channel, buf)
+    }
+
+    fn write_index(&self) -> usize {
+        // The DMA counts down from the buffer length and reloads it at the end
+        (self.len - self.transfer.remaining() as usize) % self.len
+    }
+
+    fn next_value(&mut self) -> Option<u16> {
+        if self.read == self.write_index() {
+            return None;
+        }
+        let buf = self.transfer.buffer_ptr() as *const u16;
+        compiler_fence(Ordering::Acquire);
+        let value = unsafe { buf.add(self.read).read_volatile() };
+        self.read = (self.read + 1) % self.len;
+        Some(value)
+    }
+}
+
+const fn dma_event(c: u8) -> DmaEvent {
+    match c {
+        0 => DmaEvent::C1,
+        1 => DmaEvent::C2,
+        2 => DmaEvent::C3,
+        _ => DmaEvent::C4,
+    }
+}
@@ -185,3 +185,151 @@ impl fugit_timer::Delay<1_000_000> for SysDelay {
         Ok(())
     }
 }
+
+#[cfg(feature = "async")]
+mod asynch {
+    use core::future::poll_fn;
+    use core::task::Poll;
+
+    use cortex_m::peripheral::SYST;
+    use embedded_hal_async::delay::DelayNs;
+
+    use super::{Delay, SysDelay};
+    use crate::timer::{Event, Instance};
+    use crate::waker::WakerCell;
+
+    static SYST_WAKER: WakerCell = WakerCell::new();
+
+    /// The SysTick reload value has 24 bits
+    const SYST_MAX_TICKS: u64 = 1 << 24;
+
+    /// Ticks of a `freq` Hz clock in `value` units of `1 / per_sec` seconds, rounded up
+    ///
+    /// Both factors have 32 bits, so the product can't overflow.
+    fn to_ticks(value: u32, per_sec: u32, freq: u32) -> u64 {
+        (value as u64 * freq as u64).div_ceil(per_sec as u64)
+    }
+
+    impl<TMR: Instance, const FREQ: u32> Delay<TMR, FREQ> {
+        /// Wakes the task waiting in [`DelayNs`], to be called from the timer interrupt
+        pub fn on_interrupt() {
+            if !TMR::mask_pending_interrupts().is_empty() {
+                TMR::waker().wake();
+            }
+        }
+
+        async fn wait_ticks(&mut self, mut ticks: u64) {
+            // Stops the counter if the future is dropped
+            struct OnDrop<'a, TMR: Instance, const FREQ: u32>(&'a mut Delay<TMR, FREQ>);
+            impl<TMR: Instance, const FREQ: u32> Drop for OnDrop<'_, TMR, FREQ> {
+                fn drop(&mut self) {
+                    self.0.tmr.listen_interrupt(Event::Update, false);
+                    self.0.tmr.disable_counter();
+                }
+            }
+
+            let mut delay = OnDrop(self);
+            while ticks != 0 {
+                // The counter runs from 0 to the reload value, which must not be 0
+                let chunk = ticks.clamp(2, TMR::max_auto_reload() as u64 + 1);
+                ticks = ticks.saturating_sub(chunk);
+
+                let tmr = &mut delay.0.tmr;
+                unsafe {
+                    tmr.set_auto_reload_unchecked(chunk as u32 - 1);
+                }
+                tmr.trigger_update();
+                tmr.clear_interrupt_flag(Event::Update);
+                tmr.listen_interrupt(Event::Update, true);
+                // The counter stops itself at the update event
+                tmr.start_one_pulse();
+
+                poll_fn(|cx| {
+                    TMR::waker().register(cx.waker());
+                    if delay.0.tmr.is_counter_enabled() {
+                        Poll::Pending
+                    } else {
+                        Poll::Ready(())
+                    }
+                })
+                .await;
+                delay.0.tmr.clear_interrupt_flag(Event::Update);
+            }
+        }
+    }
+
+    impl<TMR: Instance, const FREQ: u32> DelayNs for Delay<TMR, FREQ> {
+        async fn delay_ns(&mut self, ns: u32) {
+            self.wait_ticks(to_ticks(ns, 1_000_000_000, FREQ)).await
+        }
+
+        async fn delay_us(&mut self, us: u32) {
+            self.wait_ticks(to_ticks(us, 1_000_000, FREQ)).await
+        }
+
+        async fn delay_ms(&mut self, ms: u32) {
+            self.wait_ticks(to_ticks(ms, 1_000, FREQ)).await
+        }
+    }
+
+    impl SysDelay {
+        /// Wakes the task waiting in [`DelayNs`], to be called from the SysTick exception
+        pub fn on_interrupt() {
+            // NOTE(unsafe) the counter belongs to the waiting `SysDelay`
+            let mut syst = unsafe { cortex_m::Peripherals::steal().SYST };
+            syst.disable_interrupt();
+            syst.disable_counter();
+            SYST_WAKER.wake();
+        }
+
+        async fn wait_ticks(&mut self, mut ticks: u64) {
+            // Stops the counter if the future is dropped
+            struct OnDrop<'a>(&'a mut SYST);
+            impl Drop for OnDrop<'_> {
+                fn drop(&mut self) {
+                    self.0.disable_interrupt();
+                    self.0.disable_counter();
+                }
+            }
+
+            let mut syst = OnDrop(&mut self.0.tmr);
+            while ticks != 0 {
+                // SysTick counts down from the reload value to 0, which must not be 0
+                let chunk = ticks.clamp(2, SYST_MAX_TICKS);
+                ticks = ticks.saturating_sub(chunk);
+
+                syst.0.set_reload(chunk as u32 - 1);
+                syst.0.clear_current();
+                syst.0.enable_interrupt();
+                syst.0.enable_counter();
+
+                poll_fn(|cx| {
+                    SYST_WAKER.register(cx.waker());
+                    if syst.0.is_counter_enabled() {
+                        Poll::Pending
+                    } else {
+                        Poll::Ready(())
+                    }
+                })
+                .await;
+            }
+        }
+    }
+
+    impl DelayNs for SysDelay {
+        async fn delay_ns(&mut self, ns: u32) {
+            let clk = self.clk.raw();
+            self.wait_ticks(to_ticks(ns, 1_000_000_000, clk)).await
+        }
+
+        async fn delay_us(&mut self, us: u32) {
+            let clk = self.clk.raw();
+            self.wait_ticks(to_ticks(us, 1_000_000, clk)).await
+        }
+
+        async fn delay_ms(&mut self, ms: u32) {
+            let clk = self.clk.raw();
+            self.wait_ticks(to_ticks(ms, 1_000, clk)).await
+        }
+    }
+}
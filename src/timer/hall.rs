@@ -0,0 +1,273 @@
+//! Hall sensor interface and six-step commutation
+//!
+//! [`HallSensor`] feeds the XOR of the three Hall sensor inputs into channel 1. Every Hall edge
+//! captures the time since the previous edge, which is the commutation interval, and resets
+//! the counter. Channel 2 emits a trigger pulse a programmable delay after the edge. The sensor
+//! pins are switched to the input mode of their alternate function, pull resistors configured
+//! on input pins before are kept.
+//!
+//! Routed to an advanced timer (TMR1, TMR8, TMR20) through its internal trigger input, this pulse
+//! raises the COM event. The advanced timer then applies the channel configuration preloaded with
+//! [`set_commutation_step`](super::PwmHz::set_commutation_step) without software latency:
+//!
+//! ```rust
+//! let mut hall = dp.TMR3.hall_sensor_us(
+//!     (gpioa.pa6, gpioa.pa7, gpiob.pb0),
+//!     20.micros(),
+//!     &clocks,
+//! );
+//! hall.listen(Event::C1);
+//!
+//! let mut pwm = dp.TMR1.pwm_hz(
+//!     (
+//!         Channel1::new(gpioa.pa8).with_complementary(gpiob.pb13),
+//!         Channel2::new(gpioa.pa9).with_complementary(gpiob.pb14),
+//!         Channel3::new(gpioa.pa10).with_complementary(gpiob.pb15),
+//!     ),
+//!     20.kHz(),
+//!     &clocks,
+//! );
+//! // TMR3 trigger output is ITR2 of TMR1
+//! pwm.enable_commutation(Some(TriggerSource::Itr2));
+//!
+//! // in the TMR1 COM interrupt, prepare the step that follows the one just applied
+//! pwm.set_commutation_step(SIX_STEP[next_step]);
+//! ```
+
+use super::{
+    Advanced, CPin, CaptureEdge, CapturePrescaler, CaptureSource, Channel, FTimer, Instance, Ocm,
+    Pins, PwmHz, SlaveMode, TriggerSource, WithHall, WithPwm, WithSlave,
+};
+use crate::crm::Clocks;
+use crate::gpio::Input;
+use core::ops::{Deref, DerefMut};
+use fugit::TimerDurationU32;
+
+/// State of one motor phase during a commutation step
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Phase {
+    /// Both switches are off, the phase is floating
+    Off,
+    /// The high side switch is driven by the PWM, the low side switch by its complement
+    Pwm,
+    /// The low side switch is on
+    Low,
+}
+
+/// Six-step commutation sequence of phases 1, 2 and 3 for the forward direction
+///
+/// Which step belongs to which Hall sensor state depends on the motor and sensor placement.
+pub const SIX_STEP: [[Phase; 3]; 6] = [
+    [Phase::Pwm, Phase::Low, Phase::Off],
+    [Phase::Pwm, Phase::Off, Phase::Low],
+    [Phase::Off, Phase::Pwm, Phase::Low],
+    [Phase::Low, Phase::Pwm, Phase::Off],
+    [Phase::Low, Phase::Off, Phase::Pwm],
+    [Phase::Off, Phase::Low, Phase::Pwm],
+];
+
+pub trait HallSensorExt
+where
+    Self: Sized + Instance + WithHall + CPin<0> + CPin<1> + CPin<2>,
+{
+    fn hall_sensor<const FREQ: u32>(
+        self,
+        pins: (
+            impl Into<<Self as CPin<0>>::Ch<Input>>,
+            impl Into<<Self as CPin<1>>::Ch<Input>>,
+            impl Into<<Self as CPin<2>>::Ch<Input>>,
+        ),
+        delay: TimerDurationU32<FREQ>,
+        clocks: &Clocks,
+    ) -> HallSensor<Self, FREQ>;
+
+    fn hall_sensor_us(
+        self,
+        pins: (
+            impl Into<<Self as CPin<0>>::Ch<Input>>,
+            impl Into<<Self as CPin<1>>::Ch<Input>>,
+            impl Into<<Self as CPin<2>>::Ch<Input>>,
+        ),
+        delay: TimerDurationU32<1_000_000>,
+        clocks: &Clocks,
+    ) -> HallSensor<Self, 1_000_000> {
+        self.hall_sensor::<1_000_000>(pins, delay, clocks)
+    }
+}
+
+impl<TMR> HallSensorExt for TMR
+where
+    Self: Sized + Instance + WithHall + CPin<0> + CPin<1> + CPin<2>,
+{
+    fn hall_sensor<const FREQ: u32>(
+        self,
+        pins: (
+            impl Into<<Self as CPin<0>>::Ch<Input>>,
+            impl Into<<Self as CPin<1>>::Ch<Input>>,
+            impl Into<<Self as CPin<2>>::Ch<Input>>,
+        ),
+        delay: TimerDurationU32<FREQ>,
+        clocks: &Clocks,
+    ) -> HallSensor<Self, FREQ> {
+        FTimer::<Self, FREQ>::new(self, clocks).hall_sensor(pins, delay)
+    }
+}
+
+/// Hall sensor interface on channels 1-3 inputs
+pub struct HallSensor<TMR, const FREQ: u32>
+where
+    TMR: Instance + WithHall,
+{
+    timer: FTimer<TMR, FREQ>,
+}
+
+impl<TMR, const FREQ: u32> FTimer<TMR, FREQ>
+where
+    TMR: Instance + WithHall + CPin<0> + CPin<1> + CPin<2>,
+{
+    pub fn hall_sensor(
+        mut self,
+        pins: (
+            impl Into<<TMR as CPin<0>>::Ch<Input>>,
+            impl Into<<TMR as CPin<1>>::Ch<Input>>,
+            impl Into<<TMR as CPin<2>>::Ch<Input>>,
+        ),
+        delay: TimerDurationU32<FREQ>,
+    ) -> HallSensor<TMR, FREQ> {
+        let _pins = (pins.0.into(), pins.1.into(), pins.2.into());
+
+        // An overflow means the motor stopped
+        self.tmr.set_auto_reload(TMR::max_auto_reload()).unwrap();
+
+        self.tmr.enable_hall_input(true);
+        // Both edges of the XOR signal capture the interval and reset the counter
+        let c1 = Channel::C1 as u8;
+        TMR::set_input_mode(c1, CaptureSource::Trc, 0, CapturePrescaler::Div1);
+        TMR::set_capture_edge(c1, CaptureEdge::Rising);
+        self.tmr
+            .set_slave_mode(SlaveMode::Reset, TriggerSource::Ti1Edge);
+
+        // Channel 2 reference goes high `delay` after the edge and drives the trigger output
+        TMR::set_output_mode(Channel::C2 as u8, Ocm::PwmMode2);
+        self.tmr.trigger_output_on_c2();
+
+        let mut hall = HallSensor { timer: self };
+        hall.set_commutation_delay(delay);
+        TMR::enable_channel(c1, true);
+
+        hall.tmr.trigger_update();
+        hall.tmr.enable_counter();
+        hall
+    }
+}
+
+impl<TMR, const FREQ: u32> HallSensor<TMR, FREQ>
+where
+    TMR: Instance + WithHall,
+{
+    /// Set the delay from a Hall edge to the commutation trigger
+    pub fn set_commutation_delay(&mut self, delay: TimerDurationU32<FREQ>) {
+        TMR::set_cc_value(Channel::C2 as u8, delay.ticks().max(1));
+    }
+
+    /// Set the digital filter of the Hall inputs
+    pub fn set_filter(&mut self, filter: u8) {
+        let c1 = Channel::C1 as u8;
+        TMR::enable_channel(c1, false);
+        TMR::set_input_mode(c1, CaptureSource::Trc, filter, CapturePrescaler::Div1);
+        TMR::enable_channel(c1, true);
+    }
+
+    /// Get the time between the last two Hall edges
+    ///
+    /// The value is only valid after a capture, see [`Event::C1`](super::Event::C1).
+    pub fn get_interval(&self) -> TimerDurationU32<FREQ> {
+        TimerDurationU32::from_ticks(TMR::read_cc_value(Channel::C1 as u8))
+    }
+
+    /// Returns `true` if a Hall edge was captured, reading the interval clears the flag
+    pub fn is_captured(&self) -> bool {
+        TMR::is_captured(Channel::C1 as u8)
+    }
+
+    pub fn release(mut self) -> FTimer<TMR, FREQ> {
+        // stop counter
+        self.tmr.ctrl1_reset();
+        self.tmr.enable_hall_input(false);
+        self.tmr
+            .set_slave_mode(SlaveMode::Disabled, TriggerSource::Itr0);
+        self.timer
+    }
+}
+
+impl<TMR, const FREQ: u32> Deref for HallSensor<TMR, FREQ>
+where
+    TMR: Instance + WithHall,
+{
+    type Target = FTimer<TMR, FREQ>;
+    fn deref(&self) -> &Self::Target {
+        &self.timer
+    }
+}
+
+impl<TMR, const FREQ: u32> DerefMut for HallSensor<TMR, FREQ>
+where
+    TMR: Instance + WithHall,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.timer
+    }
+}
+
+impl<TMR, PINS> PwmHz<TMR, PINS>
+where
+    TMR: Instance + WithPwm + Advanced + WithSlave,
+    PINS: Pins<TMR>,
+{
+    /// Preload the channel enables and output modes, so that they are applied together
+    /// on the COM event
+    ///
+    /// With `trigger` the COM event is also raised by a rising edge of this trigger input,
+    /// otherwise only by [`commutate`](Self::commutate).
+    pub fn enable_commutation(&mut self, trigger: Option<TriggerSource>) {
+        assert!(PINS::C1 && PINS::C2 && PINS::C3);
+        if let Some(trigger) = trigger {
+            self.tmr.set_slave_mode(SlaveMode::Disabled, trigger);
+        }
+        self.tmr.set_channel_buffer(true, trigger.is_some());
+    }
+
+    /// Apply channel enables and output modes at once again
+    pub fn disable_commutation(&mut self) {
+        self.tmr.set_channel_buffer(false, false);
+    }
+
+    /// Preload the phase states of channels 1-3 for the next COM event
+    pub fn set_commutation_step(&mut self, step: [Phase; 3]) {
+        for (c, phase) in step.into_iter().enumerate() {
+            let c = c as u8;
+            match phase {
+                Phase::Off => {
+                    TMR::enable_channel(c, false);
+                    TMR::enable_nchannel(c, false);
+                }
+                Phase::Pwm => {
+                    TMR::set_output_mode(c, Ocm::PwmMode1);
+                    TMR::enable_channel(c, true);
+                    TMR::enable_nchannel(c, true);
+                }
+                Phase::Low => {
+                    TMR::set_output_mode(c, Ocm::ForceInactive);
+                    TMR::enable_channel(c, true);
+                    TMR::enable_nchannel(c, true);
+                }
+            }
+        }
+    }
+
+    /// Raise the COM event by software
+    pub fn commutate(&mut self) {
+        self.tmr.trigger_commutation();
+    }
+}
@@ -0,0 +1,408 @@
+//! RTIC monotonics
+//!
+//! Timer queue backends for [`rtic-time`](rtic_time), usable with RTIC 2 and anything else
+//! built on `rtic-monotonics`:
+//!
+//! - [`MonoTimer64`] runs on any timer with at least two channels. Channel 2 interrupts at
+//!   the middle and the overflow at the end of every 16-bit period; counting these half
+//!   periods extends the counter to a 64-bit timestamp that never wraps. Channel 1 is the
+//!   compare channel of the queue.
+//! - [`MonoTimer`] uses TMR2 or TMR5 with 32 bits, in plus mode where the timer has one,
+//!   without overflow tracking, so the timestamp wraps, e.g. after 71 minutes at 1 MHz.
+//! - [`SysMonoTimer`] is the fallback on SysTick, interrupting once every tick.
+//!
+//! The timer interrupt has to call `on_interrupt` of the monotonic:
+//!
+//! ```rust
+//! type Mono = MonoTimer64<pac::TMR3, 1_000_000>;
+//!
+//! dp.TMR3.monotonic64_us(&clocks);
+//!
+//! #[interrupt]
+//! fn TMR3_GLOBAL() {
+//!     unsafe { Mono::on_interrupt() };
+//! }
+//!
+//! // in a task
+//! Mono::delay(10.millis()).await;
+//! ```
+//!
+//! TMR1, TMR8 and TMR20 have separate interrupt vectors for the overflow and for the
+//! channels, e.g. `TMR1_OVF_TMR10` and `TMR1_CH`. [`MonoTimer64`] uses both, so
+//! `on_interrupt` must be called from both handlers:
+//!
+//! ```rust
+//! type Mono = MonoTimer64<pac::TMR1, 1_000_000>;
+//!
+//! #[interrupt]
+//! fn TMR1_OVF_TMR10() {
+//!     unsafe { Mono::on_interrupt() };
+//! }
+//!
+//! #[interrupt]
+//! fn TMR1_CH() {
+//!     unsafe { Mono::on_interrupt() };
+//! }
+//! ```
+//!
+//! A timer can only be started once and with a single tick rate, which must divide the
+//! timer clock.
+
+#[cfg(any(all(feature = "tmr2", not(feature = "at32f425")), feature = "tmr5"))]
+use super::WithPlusMode;
+use super::{FTimer, General, Timer, WithPwmCommon};
+use crate::crm::Clocks;
+use crate::pac;
+use core::cell::Cell;
+use core::marker::PhantomData;
+use cortex_m::interrupt::{self, Mutex};
+use cortex_m::peripheral::{SCB, SYST};
+use fugit::{TimerDurationU32, TimerDurationU64, TimerInstantU32, TimerInstantU64};
+use rtic_time::half_period_counter::calculate_now;
+use rtic_time::monotonic::TimerQueueBasedMonotonic;
+use rtic_time::timer_queue::{TimerQueue, TimerQueueBackend};
+
+// ISTS and IDEN bits
+const OVF: u32 = 1 << 0;
+const C1: u32 = 1 << 1;
+const C2: u32 = 1 << 2;
+
+pub trait MonoTimer64Ext: Sized {
+    type Backend: TimerQueueBackend<Ticks = u64>;
+
+    /// Start the timer as 64-bit monotonic with `FREQ` ticks per second
+    fn monotonic64<const FREQ: u32>(self, clocks: &Clocks) -> MonoTimer64<Self, FREQ>;
+
+    /// Start the timer as 64-bit monotonic with a precision of 1 μs
+    fn monotonic64_us(self, clocks: &Clocks) -> MonoTimer64<Self, 1_000_000> {
+        self.monotonic64::<1_000_000>(clocks)
+    }
+}
+
+pub trait MonoTimerExt: Sized {
+    type Backend: TimerQueueBackend<Ticks = u32>;
+
+    /// Start the timer as 32-bit monotonic with `FREQ` ticks per second
+    fn monotonic<const FREQ: u32>(self, clocks: &Clocks) -> MonoTimer<Self, FREQ>;
+
+    /// Start the timer as 32-bit monotonic with a precision of 1 μs
+    fn monotonic_us(self, clocks: &Clocks) -> MonoTimer<Self, 1_000_000> {
+        self.monotonic::<1_000_000>(clocks)
+    }
+}
+
+pub trait SysMonoTimerExt: Sized {
+    /// Start SysTick as monotonic with `FREQ` ticks per second
+    ///
+    /// SysTick interrupts on every tick, so keep `FREQ` low, e.g. 1 kHz.
+    fn monotonic<const FREQ: u32>(self, clocks: &Clocks) -> SysMonoTimer<FREQ>;
+}
+
+/// 64-bit monotonic with half-period overflow tracking
+pub struct MonoTimer64<TMR, const FREQ: u32>(PhantomData<TMR>);
+
+impl<TMR: MonoTimer64Ext, const FREQ: u32> MonoTimer64<TMR, FREQ> {
+    /// Handle the timer interrupt
+    ///
+    /// On timers with separate overflow and channel interrupts, call it from both.
+    ///
+    /// # Safety
+    ///
+    /// Must only be called from the interrupts of the timer.
+    pub unsafe fn on_interrupt() {
+        TMR::Backend::timer_queue().on_monotonic_interrupt();
+    }
+}
+
+impl<TMR: MonoTimer64Ext, const FREQ: u32> TimerQueueBasedMonotonic for MonoTimer64<TMR, FREQ> {
+    type Backend = TMR::Backend;
+    type Instant = TimerInstantU64<FREQ>;
+    type Duration = TimerDurationU64<FREQ>;
+}
+
+/// 32-bit monotonic on TMR2 or TMR5
+pub struct MonoTimer<TMR, const FREQ: u32>(PhantomData<TMR>);
+
+impl<TMR: MonoTimerExt, const FREQ: u32> MonoTimer<TMR, FREQ> {
+    /// Handle the timer interrupt
+    ///
+    /// # Safety
+    ///
+    /// Must only be called from the interrupt of the timer.
+    pub unsafe fn on_interrupt() {
+        TMR::Backend::timer_queue().on_monotonic_interrupt();
+    }
+}
+
+impl<TMR: MonoTimerExt, const FREQ: u32> TimerQueueBasedMonotonic for MonoTimer<TMR, FREQ> {
+    type Backend = TMR::Backend;
+    type Instant = TimerInstantU32<FREQ>;
+    type Duration = TimerDurationU32<FREQ>;
+}
+
+/// 64-bit monotonic on SysTick
+pub struct SysMonoTimer<const FREQ: u32>(());
+
+impl<const FREQ: u32> SysMonoTimer<FREQ> {
+    /// Handle the SysTick exception
+    ///
+    /// # Safety
+    ///
+    /// Must only be called from the SysTick exception.
+    pub unsafe fn on_interrupt() {
+        SysTickBackend::timer_queue().on_monotonic_interrupt();
+    }
+}
+
+impl<const FREQ: u32> TimerQueueBasedMonotonic for SysMonoTimer<FREQ> {
+    type Backend = SysTickBackend;
+    type Instant = TimerInstantU64<FREQ>;
+    type Duration = TimerDurationU64<FREQ>;
+}
+
+impl SysMonoTimerExt for SYST {
+    fn monotonic<const FREQ: u32>(self, clocks: &Clocks) -> SysMonoTimer<FREQ> {
+        let mut timer = Timer::syst(self, clocks);
+        assert!(timer.clk.raw() % FREQ == 0);
+        timer.tmr.set_reload(timer.clk.raw() / FREQ - 1);
+        timer.tmr.clear_current();
+
+        SysTickBackend::timer_queue().initialize(SysTickBackend);
+        timer.tmr.enable_interrupt();
+        timer.tmr.enable_counter();
+        // The backend owns SysTick from now on
+        core::mem::forget(timer);
+        SysMonoTimer(())
+    }
+}
+
+/// Timer queue backend of [`SysMonoTimer`]
+pub struct SysTickBackend;
+
+impl SysTickBackend {
+    fn ticks() -> &'static Mutex<Cell<u64>> {
+        static TICKS: Mutex<Cell<u64>> = Mutex::new(Cell::new(0));
+        &TICKS
+    }
+}
+
+impl TimerQueueBackend for SysTickBackend {
+    type Ticks = u64;
+
+    fn now() -> u64 {
+        interrupt::free(|cs| Self::ticks().borrow(cs).get())
+    }
+
+    // Every tick interrupts, so the queue is checked anyway
+    fn set_compare(_instant: u64) {}
+
+    fn clear_compare_flag() {}
+
+    fn pend_interrupt() {
+        SCB::set_pendst();
+    }
+
+    fn on_interrupt() {
+        // Reading COUNTFLAG clears it, pended exceptions leave it untouched
+        if unsafe { (*SYST::PTR).csr.read() } & (1 << 16) != 0 {
+            interrupt::free(|cs| {
+                let ticks = Self::ticks().borrow(cs);
+                ticks.set(ticks.get() + 1);
+            });
+        }
+    }
+
+    fn timer_queue() -> &'static TimerQueue<Self> {
+        static QUEUE: TimerQueue<SysTickBackend> = TimerQueue::new();
+        &QUEUE
+    }
+}
+
+macro_rules! mono64 {
+    ($TMR:ty, $Backend:ident) => {
+        /// Timer queue backend of [`MonoTimer64`] on this timer
+        pub struct $Backend;
+
+        impl $Backend {
+            fn half_periods() -> &'static Mutex<Cell<u64>> {
+                static HALF_PERIODS: Mutex<Cell<u64>> = Mutex::new(Cell::new(0));
+                &HALF_PERIODS
+            }
+        }
+
+        impl MonoTimer64Ext for $TMR {
+            type Backend = $Backend;
+
+            fn monotonic64<const FREQ: u32>(self, clocks: &Clocks) -> MonoTimer64<Self, FREQ> {
+                let mut timer = FTimer::<Self, FREQ>::new(self, clocks);
+                timer.tmr.set_auto_reload(u16::MAX as u32).unwrap();
+                // Channel 2 marks the middle of the period
+                <$TMR>::set_cc_value(1, 0x8000);
+                timer.tmr.trigger_update();
+
+                $Backend::timer_queue().initialize($Backend);
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                tmr.ists().write(|w| unsafe { w.bits(0) });
+                tmr.iden()
+                    .modify(|r, w| unsafe { w.bits(r.bits() | OVF | C1 | C2) });
+                timer.tmr.enable_counter();
+                // The backend owns the timer from now on
+                core::mem::forget(timer);
+                MonoTimer64(PhantomData)
+            }
+        }
+
+        impl TimerQueueBackend for $Backend {
+            type Ticks = u64;
+
+            fn now() -> u64 {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                calculate_now(
+                    || interrupt::free(|cs| Self::half_periods().borrow(cs).get()),
+                    || tmr.cval().read().bits() as u16,
+                )
+            }
+
+            fn set_compare(instant: u64) {
+                let now = Self::now();
+                // Instants beyond the current period are programmed again by one of the
+                // half-period interrupts
+                let val = if instant.wrapping_sub(now) <= u16::MAX as u64 {
+                    instant as u16
+                } else {
+                    0
+                };
+                <$TMR>::set_cc_value(0, val as u32);
+            }
+
+            fn clear_compare_flag() {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                tmr.ists().write(|w| unsafe { w.bits(!C1) });
+            }
+
+            fn pend_interrupt() {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                // C1SWTR raises the channel 1 interrupt
+                tmr.swevt().write(|w| unsafe { w.bits(C1) });
+            }
+
+            fn on_interrupt() {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                let flags = tmr.ists().read().bits();
+                // The overflow makes the count even, the middle of the period odd
+                if flags & OVF != 0 {
+                    tmr.ists().write(|w| unsafe { w.bits(!OVF) });
+                    interrupt::free(|cs| {
+                        let half_periods = Self::half_periods().borrow(cs);
+                        let prev = half_periods.get();
+                        assert!(prev % 2 == 1, "Monotonic must have missed an interrupt!");
+                        half_periods.set(prev + 1);
+                    });
+                }
+                if flags & C2 != 0 {
+                    tmr.ists().write(|w| unsafe { w.bits(!C2) });
+                    interrupt::free(|cs| {
+                        let half_periods = Self::half_periods().borrow(cs);
+                        let prev = half_periods.get();
+                        assert!(prev % 2 == 0, "Monotonic must have missed an interrupt!");
+                        half_periods.set(prev + 1);
+                    });
+                }
+            }
+
+            fn timer_queue() -> &'static TimerQueue<Self> {
+                static QUEUE: TimerQueue<$Backend> = TimerQueue::new();
+                &QUEUE
+            }
+        }
+    };
+}
+
+macro_rules! mono32 {
+    ($TMR:ty, $Backend:ident $(, plus: $pmen:ident)?) => {
+        /// Timer queue backend of [`MonoTimer`] on this timer
+        pub struct $Backend;
+
+        impl MonoTimerExt for $TMR {
+            type Backend = $Backend;
+
+            fn monotonic<const FREQ: u32>(self, clocks: &Clocks) -> MonoTimer<Self, FREQ> {
+                let mut timer = FTimer::<Self, FREQ>::new(self, clocks);
+                $(
+                    let $pmen = ();
+                    timer.tmr.enable_plus_mode(true);
+                )?
+                timer.tmr.set_auto_reload(u32::MAX).unwrap();
+                timer.tmr.trigger_update();
+
+                $Backend::timer_queue().initialize($Backend);
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                tmr.ists().write(|w| unsafe { w.bits(0) });
+                tmr.iden().modify(|r, w| unsafe { w.bits(r.bits() | C1) });
+                timer.tmr.enable_counter();
+                // The backend owns the timer from now on
+                core::mem::forget(timer);
+                MonoTimer(PhantomData)
+            }
+        }
+
+        impl TimerQueueBackend for $Backend {
+            type Ticks = u32;
+
+            fn now() -> u32 {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                tmr.cval().read().bits()
+            }
+
+            fn set_compare(instant: u32) {
+                <$TMR>::set_cc_value(0, instant);
+            }
+
+            fn clear_compare_flag() {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                tmr.ists().write(|w| unsafe { w.bits(!C1) });
+            }
+
+            fn pend_interrupt() {
+                let tmr = unsafe { &*<$TMR>::ptr() };
+                // C1SWTR raises the channel 1 interrupt
+                tmr.swevt().write(|w| unsafe { w.bits(C1) });
+            }
+
+            fn timer_queue() -> &'static TimerQueue<Self> {
+                static QUEUE: TimerQueue<$Backend> = TimerQueue::new();
+                &QUEUE
+            }
+        }
+    };
+}
+
+#[cfg(feature = "tmr1")]
+mono64!(pac::TMR1, Tmr1Backend64);
+#[cfg(feature = "tmr2")]
+mono64!(pac::TMR2, Tmr2Backend64);
+#[cfg(feature = "tmr3")]
+mono64!(pac::TMR3, Tmr3Backend64);
+#[cfg(feature = "tmr4")]
+mono64!(pac::TMR4, Tmr4Backend64);
+#[cfg(feature = "tmr5")]
+mono64!(pac::TMR5, Tmr5Backend64);
+#[cfg(feature = "tmr8")]
+mono64!(pac::TMR8, Tmr8Backend64);
+#[cfg(feature = "tmr9")]
+mono64!(pac::TMR9, Tmr9Backend64);
+#[cfg(feature = "tmr12")]
+mono64!(pac::TMR12, Tmr12Backend64);
+#[cfg(feature = "tmr15")]
+mono64!(pac::TMR15, Tmr15Backend64);
+#[cfg(feature = "tmr20")]
+mono64!(pac::TMR20, Tmr20Backend64);
+
+#[cfg(feature = "tmr2")]
+#[cfg(not(feature = "at32f425"))]
+mono32!(pac::TMR2, Tmr2Backend, plus: _pmen);
+#[cfg(feature = "tmr2")]
+#[cfg(feature = "at32f425")]
+mono32!(pac::TMR2, Tmr2Backend);
+#[cfg(feature = "tmr5")]
+mono32!(pac::TMR5, Tmr5Backend, plus: _pmen);
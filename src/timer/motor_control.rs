@@ -0,0 +1,285 @@
+//! Motor control features of advanced timers
+//!
+//! Advanced timers (TMR1, TMR8, TMR20, and TMR15-17 on channel 1) drive complementary PWM
+//! pairs with a dead time inserted between switching off one output and switching on the
+//! other one. The break input (BKIN) forces all outputs into their idle states in hardware,
+//! e.g. on over current.
+//!
+//! ```rust
+//! let mut pwm = dp.TMR1.pwm_hz(
+//!     Channel1::new(gpioa.pa8).with_complementary(gpiob.pb13),
+//!     20.kHz(),
+//!     &clocks,
+//! );
+//! let brk = pwm.break_pin(gpiob.pb12.into_pull_up_input());
+//! pwm.configure_motor_control(
+//!     MotorControlConfig::new()
+//!         .dead_time(500.nanos())
+//!         .break_input(BreakConfig::new(Polarity::ActiveLow))
+//!         .lock(LockLevel::Level1),
+//! )
+//! .unwrap();
+//! pwm.listen(Event::Break);
+//! ```
+//!
+//! The break pin is switched to the input mode of its alternate function, a pull resistor
+//! configured on an input pin before is kept. [`BreakPin::release`] gives it back.
+
+use super::pwm::{pack_ceil_dead_time, unpack_dead_time};
+use super::{Advanced, Error, Event, FTimer, Instance, Polarity, Timer};
+use crate::gpio::alt::TmrBkin;
+use fugit::{HertzU32 as Hertz, NanosDurationU32};
+
+// BRK register fields
+const DTC: u32 = 0xff;
+const WPC: u32 = 0b11 << 8;
+const FCSODIS: u32 = 1 << 10;
+const FCSOEN: u32 = 1 << 11;
+const BRKEN: u32 = 1 << 12;
+const BRKV: u32 = 1 << 13;
+const AOEN: u32 = 1 << 14;
+const BKF: u32 = 0xf << 16;
+
+/// Protection of the break and dead time configuration against software errors
+///
+/// Once written, the lock level can only be changed by a reset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum LockLevel {
+    /// No write protection
+    Off = 0,
+    /// Dead time, break enable, break polarity and automatic output enable are locked
+    Level1 = 1,
+    /// As `Level1`, idle states and off-state selections are also locked
+    Level2 = 2,
+    /// As `Level2`, channel polarity and output modes are also locked
+    Level3 = 3,
+}
+
+/// Break input configuration
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BreakConfig {
+    /// Level of BKIN that disables the outputs
+    pub polarity: Polarity,
+    /// Digital filter of BKIN, `0` disables filtering
+    ///
+    /// Only available on AT32F402/405/423/435/437, ignored on other devices.
+    pub filter: u8,
+}
+
+impl BreakConfig {
+    pub const fn new(polarity: Polarity) -> Self {
+        Self {
+            polarity,
+            filter: 0,
+        }
+    }
+
+    pub const fn filter(mut self, filter: u8) -> Self {
+        self.filter = filter;
+        self
+    }
+}
+
+/// Break input pin of `TMR`, connected with `break_pin`
+pub struct BreakPin<TMR: TmrBkin> {
+    pin: TMR::Bkin,
+}
+
+impl<TMR: TmrBkin> BreakPin<TMR> {
+    /// Get the pin back, e.g. to convert it into another mode with `TryFrom`
+    pub fn release(self) -> TMR::Bkin {
+        self.pin
+    }
+}
+
+/// Motor control configuration of an advanced timer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MotorControlConfig {
+    dead_time: NanosDurationU32,
+    break_input: Option<BreakConfig>,
+    auto_output: bool,
+    off_state_idle: bool,
+    off_state_run: bool,
+    lock: LockLevel,
+}
+
+impl Default for MotorControlConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MotorControlConfig {
+    pub const fn new() -> Self {
+        Self {
+            dead_time: NanosDurationU32::from_ticks(0),
+            break_input: None,
+            auto_output: false,
+            off_state_idle: false,
+            off_state_run: false,
+            lock: LockLevel::Off,
+        }
+    }
+
+    /// Dead time between the primary and the complementary output
+    pub const fn dead_time(mut self, dead_time: NanosDurationU32) -> Self {
+        self.dead_time = dead_time;
+        self
+    }
+
+    /// Enable the break input
+    pub const fn break_input(mut self, config: BreakConfig) -> Self {
+        self.break_input = Some(config);
+        self
+    }
+
+    /// Re-enable the outputs on the next update event after a break is gone
+    pub const fn auto_output(mut self, enable: bool) -> Self {
+        self.auto_output = enable;
+        self
+    }
+
+    /// Drive disabled outputs to their inactive level instead of releasing them,
+    /// `idle` while the outputs are off and `run` while they are on
+    pub const fn off_state(mut self, idle: bool, run: bool) -> Self {
+        self.off_state_idle = idle;
+        self.off_state_run = run;
+        self
+    }
+
+    /// Write protection applied after the configuration
+    pub const fn lock(mut self, lock: LockLevel) -> Self {
+        self.lock = lock;
+        self
+    }
+}
+
+macro_rules! motor_control_impl {
+    () => {
+        /// Apply the motor control configuration
+        ///
+        /// The clock division of the timer may be changed to reach the dead time,
+        /// which also scales the sampling clock of the input filters.
+        pub fn configure_motor_control(&mut self, config: MotorControlConfig) -> Result<(), Error> {
+            self.set_dead_time_duration(config.dead_time)?;
+
+            let mut bits = 0;
+            if let Some(brk) = config.break_input {
+                bits |= BRKEN | ((brk.filter as u32 & 0xf) << 16);
+                if brk.polarity == Polarity::ActiveHigh {
+                    bits |= BRKV;
+                }
+            }
+            if config.auto_output {
+                bits |= AOEN;
+            }
+            if config.off_state_idle {
+                bits |= FCSODIS;
+            }
+            if config.off_state_run {
+                bits |= FCSOEN;
+            }
+            self.tmr
+                .modify_brk(BRKEN | BKF | BRKV | AOEN | FCSODIS | FCSOEN, bits);
+
+            // The lock must be written last, it blocks writes to the fields above
+            self.tmr.modify_brk(WPC, (config.lock as u32) << 8);
+            Ok(())
+        }
+
+        /// Set the dead time of complementary outputs
+        pub fn set_dead_time_duration(&mut self, dead_time: NanosDurationU32) -> Result<(), Error> {
+            let (div, bits) =
+                dead_time_bits(self.timer_clock(), dead_time).ok_or(Error::WrongDeadTime)?;
+            self.tmr.set_clock_division(div);
+            self.tmr.modify_brk(DTC, bits as u32);
+            Ok(())
+        }
+
+        /// Get the dead time of complementary outputs
+        pub fn get_dead_time_duration(&self) -> NanosDurationU32 {
+            let dts = (self.timer_clock().raw() >> self.tmr.read_clock_division()) as u64;
+            let ticks = unpack_dead_time((self.tmr.read_brk() & DTC) as u8) as u64;
+            NanosDurationU32::from_ticks((ticks * 1_000_000_000 / dts) as u32)
+        }
+
+        /// Connect the break input pin
+        ///
+        /// The pin is switched to the input mode of its alternate function.
+        pub fn break_pin(&mut self, pin: impl Into<TMR::Bkin>) -> BreakPin<TMR>
+        where
+            TMR: TmrBkin,
+        {
+            BreakPin { pin: pin.into() }
+        }
+
+        /// Enable the break input
+        pub fn enable_break(&mut self, config: BreakConfig) {
+            let mut bits = BRKEN | ((config.filter as u32 & 0xf) << 16);
+            if config.polarity == Polarity::ActiveHigh {
+                bits |= BRKV;
+            }
+            self.tmr.modify_brk(BRKEN | BKF | BRKV, bits);
+        }
+
+        /// Disable the break input
+        pub fn disable_break(&mut self) {
+            self.tmr.modify_brk(BRKEN, 0);
+        }
+
+        /// Returns `true` once a break occurred, until cleared with [`clear_break`](Self::clear_break)
+        pub fn is_break_active(&self) -> bool {
+            self.tmr.get_interrupt_flag().contains(Event::Break)
+        }
+
+        /// Clear the break flag and, unless automatic output enable is on,
+        /// enable the outputs again
+        ///
+        /// The outputs stay off while the break input is still active.
+        pub fn clear_break(&mut self) {
+            self.tmr.clear_interrupt_flag(Event::Break);
+            if self.tmr.read_brk() & AOEN == 0 {
+                self.tmr.enable_output();
+            }
+        }
+
+        /// Enable all outputs of the timer
+        pub fn enable_outputs(&mut self) {
+            self.tmr.enable_output();
+        }
+
+        /// Disable all outputs of the timer, they go to their idle states
+        pub fn disable_outputs(&mut self) {
+            self.tmr.disable_output();
+        }
+    };
+}
+
+impl<TMR: Instance + Advanced> Timer<TMR> {
+    motor_control_impl!();
+
+    fn timer_clock(&self) -> Hertz {
+        self.clk
+    }
+}
+
+impl<TMR: Instance + Advanced, const FREQ: u32> FTimer<TMR, FREQ> {
+    motor_control_impl!();
+
+    fn timer_clock(&self) -> Hertz {
+        Hertz::from_raw(FREQ * (self.tmr.read_prescaler() as u32 + 1))
+    }
+}
+
+/// Find the smallest clock division that can generate `dead_time`
+/// and return it with the DTC bits
+fn dead_time_bits(clk: Hertz, dead_time: NanosDurationU32) -> Option<(u8, u8)> {
+    (0..3).find_map(|div| {
+        let dts = (clk.raw() >> div) as u64;
+        let ticks = (dead_time.ticks() as u64 * dts + 999_999_999) / 1_000_000_000;
+        (ticks <= 1008).then(|| (div, pack_ceil_dead_time(ticks as u16)))
+    })
+}
@@ -0,0 +1,235 @@
+//! One-pulse mode
+//!
+//! [`OnePulse`] outputs a single pulse of programmable width on a timer channel after a
+//! programmable delay. The counter stops by itself at the end of the pulse, so every pulse
+//! has to be started again either by software with [`fire`](OnePulse::fire) or by an edge
+//! on channel 1 or 2 input, set up with [`trigger_from`](OnePulse::trigger_from) and a
+//! [`CaptureInput`](super::CaptureInput):
+//!
+//! ```rust
+//! let mut pulse = dp.TMR3.one_pulse_us(
+//!     Channel2::new(gpioa.pa7),
+//!     10.micros(),
+//!     20.micros(),
+//!     &clocks,
+//! )?;
+//! pulse.fire();
+//! let trigger = CaptureInput1::new(gpioa.pa6);
+//! pulse.trigger_from(&trigger, CaptureEdge::Rising);
+//! ```
+//!
+//! The delay is measured from the start of the counter, so it must be at least 1 tick,
+//! and the delay plus the width must fit into the counter. Otherwise creating the
+//! [`OnePulse`] fails with [`Error::ZeroDelay`] or [`Error::WrongAutoReload`].
+
+use super::{
+    CPin, CaptureEdge, CaptureInput, CapturePrescaler, CaptureSource, Channel, ChannelBuilder,
+    Error, FTimer, Instance, Ocm, Polarity, SlaveMode, TriggerSource, WithCapture, WithPwm,
+    WithSlave,
+};
+use crate::crm::Clocks;
+use core::ops::{Deref, DerefMut};
+use fugit::TimerDurationU32;
+
+pub trait OnePulseExt
+where
+    Self: Sized + Instance + WithPwm,
+{
+    fn one_pulse<const C: u8, const COMP: bool, Otype, const FREQ: u32>(
+        self,
+        channel: ChannelBuilder<Self, C, COMP, Otype>,
+        delay: TimerDurationU32<FREQ>,
+        width: TimerDurationU32<FREQ>,
+        clocks: &Clocks,
+    ) -> Result<OnePulse<Self, C, FREQ>, Error>;
+
+    fn one_pulse_us<const C: u8, const COMP: bool, Otype>(
+        self,
+        channel: ChannelBuilder<Self, C, COMP, Otype>,
+        delay: TimerDurationU32<1_000_000>,
+        width: TimerDurationU32<1_000_000>,
+        clocks: &Clocks,
+    ) -> Result<OnePulse<Self, C, 1_000_000>, Error> {
+        self.one_pulse(channel, delay, width, clocks)
+    }
+}
+
+impl<TMR> OnePulseExt for TMR
+where
+    Self: Sized + Instance + WithPwm,
+{
+    fn one_pulse<const C: u8, const COMP: bool, Otype, const FREQ: u32>(
+        self,
+        channel: ChannelBuilder<Self, C, COMP, Otype>,
+        delay: TimerDurationU32<FREQ>,
+        width: TimerDurationU32<FREQ>,
+        clocks: &Clocks,
+    ) -> Result<OnePulse<Self, C, FREQ>, Error> {
+        FTimer::<Self, FREQ>::new(self, clocks).one_pulse(channel, delay, width)
+    }
+}
+
+pub struct OnePulse<TMR, const C: u8, const FREQ: u32>
+where
+    TMR: Instance + WithPwm,
+{
+    timer: FTimer<TMR, FREQ>,
+}
+
+impl<TMR: Instance + WithPwm, const FREQ: u32> FTimer<TMR, FREQ> {
+    /// Configure the channel for one-pulse output
+    ///
+    /// Fails if the delay is 0 or the delay plus the width does not fit into the counter.
+    pub fn one_pulse<const C: u8, const COMP: bool, Otype>(
+        mut self,
+        _channel: ChannelBuilder<TMR, C, COMP, Otype>,
+        delay: TimerDurationU32<FREQ>,
+        width: TimerDurationU32<FREQ>,
+    ) -> Result<OnePulse<TMR, C, FREQ>, Error> {
+        // The output is inactive until the counter reaches the delay and active
+        // until the update event, which stops the counter
+        self.tmr
+            .preload_output_channel_in_mode(channel(C), Ocm::PwmMode2);
+        self.tmr.enable_preload(true);
+        self.tmr.enable_one_pulse(true);
+        self.tmr.enable_auto_output();
+
+        let mut pulse = OnePulse { timer: self };
+        pulse.set_timing(delay, width)?;
+        TMR::enable_channel(C, true);
+        Ok(pulse)
+    }
+}
+
+impl<TMR, const C: u8, const FREQ: u32> OnePulse<TMR, C, FREQ>
+where
+    TMR: Instance + WithPwm,
+{
+    /// Set the delay from the start of the counter and the width of the pulse
+    ///
+    /// Returns an error if the delay is 0 or the total length does not fit into the counter.
+    /// New values are used starting from the next pulse.
+    pub fn set_timing(
+        &mut self,
+        delay: TimerDurationU32<FREQ>,
+        width: TimerDurationU32<FREQ>,
+    ) -> Result<(), Error> {
+        // With a delay of 0 the compare never matches after the start and no pulse appears
+        if delay.ticks() == 0 {
+            return Err(Error::ZeroDelay);
+        }
+        let arr = (delay.ticks() - 1)
+            .checked_add(width.ticks())
+            .ok_or(Error::WrongAutoReload)?;
+        self.tmr.set_auto_reload(arr)?;
+        TMR::set_cc_value(C, delay.ticks());
+        if !self.tmr.is_counter_enabled() {
+            // Load the preloaded values right away if no pulse is in progress
+            self.tmr.trigger_update();
+        }
+        Ok(())
+    }
+
+    /// Get the delay from the start of the counter to the pulse
+    pub fn get_delay(&self) -> TimerDurationU32<FREQ> {
+        TimerDurationU32::from_ticks(TMR::read_cc_value(C))
+    }
+
+    /// Get the width of the pulse
+    pub fn get_width(&self) -> TimerDurationU32<FREQ> {
+        TimerDurationU32::from_ticks(TMR::read_auto_reload() + 1 - TMR::read_cc_value(C))
+    }
+
+    /// Set the polarity of the active state of the pulse
+    #[inline]
+    pub fn set_polarity(&mut self, p: Polarity) {
+        TMR::set_channel_polarity(C, p);
+    }
+
+    /// Start a pulse by software
+    ///
+    /// Does nothing if a pulse is already in progress
+    pub fn fire(&mut self) {
+        if !self.tmr.is_counter_enabled() {
+            self.tmr.start_one_pulse();
+        }
+    }
+
+    /// Returns `true` while the delay or the pulse is in progress
+    pub fn is_busy(&self) -> bool {
+        self.tmr.is_counter_enabled()
+    }
+
+    /// Abort the pulse in progress and re-arm the timer for the next start
+    pub fn cancel(&mut self) {
+        self.tmr.disable_counter();
+        self.tmr.reset_counter();
+    }
+
+    pub fn release(mut self) -> FTimer<TMR, FREQ> {
+        // stop counter
+        self.tmr.ctrl1_reset();
+        self.timer
+    }
+}
+
+impl<TMR, const C: u8, const FREQ: u32> OnePulse<TMR, C, FREQ>
+where
+    TMR: Instance + WithPwm + WithCapture + WithSlave,
+{
+    /// Start the pulse by hardware on `edge` of channel `T` input
+    ///
+    /// Only channels 1 and 2 can be used as trigger and the output must be on another channel.
+    /// The timer re-arms itself at the end of every pulse. The input is only borrowed,
+    /// keep it until [`trigger_by_software`](Self::trigger_by_software) and release it then.
+    pub fn trigger_from<const T: u8>(&mut self, _input: &CaptureInput<TMR, T>, edge: CaptureEdge)
+    where
+        TMR: CPin<T>,
+    {
+        assert!(T < 2 && T != C);
+        TMR::enable_channel(T, false);
+        TMR::set_input_mode(T, CaptureSource::Direct, 0, CapturePrescaler::Div1);
+        TMR::set_capture_edge(T, edge);
+        TMR::enable_channel(T, true);
+        let trigger = if T == 0 {
+            TriggerSource::Ti1
+        } else {
+            TriggerSource::Ti2
+        };
+        self.tmr.set_slave_mode(SlaveMode::Trigger, trigger);
+    }
+
+    /// Stop reacting to the trigger input, only [`fire`](Self::fire) starts a pulse
+    pub fn trigger_by_software(&mut self) {
+        self.tmr
+            .set_slave_mode(SlaveMode::Disabled, TriggerSource::Itr0);
+    }
+}
+
+impl<TMR, const C: u8, const FREQ: u32> Deref for OnePulse<TMR, C, FREQ>
+where
+    TMR: Instance + WithPwm,
+{
+    type Target = FTimer<TMR, FREQ>;
+    fn deref(&self) -> &Self::Target {
+        &self.timer
+    }
+}
+
+impl<TMR, const C: u8, const FREQ: u32> DerefMut for OnePulse<TMR, C, FREQ>
+where
+    TMR: Instance + WithPwm,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.timer
+    }
+}
+
+const fn channel(c: u8) -> Channel {
+    match c {
+        0 => Channel::C1,
+        1 => Channel::C2,
+        2 => Channel::C3,
+        _ => Channel::C4,
+    }
+}
@@ -0,0 +1,266 @@
+//! Output compare
+//!
+//! [`OutputCompare`] lets the counter run freely over its whole range and schedules single
+//! events on the channels. When the counter matches the scheduled tick, the channel output is
+//! set, cleared or toggled by hardware, independent of interrupt latency, and the channel
+//! flag is raised. Channels without a pin can be used for interrupts only.
+//!
+//! [`on_match`](OutputCompare::on_match) is meant to be called from the timer interrupt.
+//! Its callback returns the next event of the channel, which rearms it, e.g. for a pulse
+//! train or the bit timing of a software UART:
+//!
+//! ```rust
+//! let mut oc = dp.TMR3.output_compare_us(Channel1::new(gpioa.pa6), &clocks);
+//! let now = oc.now();
+//! oc.schedule(Channel::C1, now + 100, CompareAction::Toggle);
+//! oc.listen(Event::C1);
+//!
+//! // in the TMR3 interrupt, toggle every 50 μs
+//! oc.on_match(|_channel, at| Some((at + 50, CompareAction::Toggle)));
+//! ```
+//!
+//! Ticks are counter values, they wrap at the end of the counter range.
+
+use super::{Channel, Event, FTimer, Instance, Ocm, Pins, WithPwm};
+use crate::crm::Clocks;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use fugit::TimerDurationU32;
+
+/// What a channel does when the counter matches
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CompareAction {
+    /// Only raise the channel flag and interrupt
+    Interrupt,
+    /// Drive the output to its active level
+    Set,
+    /// Drive the output to its inactive level
+    Clear,
+    /// Toggle the output
+    Toggle,
+}
+
+impl From<CompareAction> for Ocm {
+    fn from(action: CompareAction) -> Self {
+        match action {
+            CompareAction::Interrupt => Ocm::Frozen,
+            CompareAction::Set => Ocm::ActiveOnMatch,
+            CompareAction::Clear => Ocm::InactiveOnMatch,
+            CompareAction::Toggle => Ocm::Toggle,
+        }
+    }
+}
+
+pub trait OutputCompareExt
+where
+    Self: Sized + Instance + WithPwm,
+{
+    fn output_compare<PINS, const FREQ: u32>(
+        self,
+        pins: PINS,
+        clocks: &Clocks,
+    ) -> OutputCompare<Self, PINS, FREQ>
+    where
+        PINS: Pins<Self>;
+
+    fn output_compare_us<PINS>(
+        self,
+        pins: PINS,
+        clocks: &Clocks,
+    ) -> OutputCompare<Self, PINS, 1_000_000>
+    where
+        PINS: Pins<Self>,
+    {
+        self.output_compare::<_, 1_000_000>(pins, clocks)
+    }
+}
+
+impl<TMR> OutputCompareExt for TMR
+where
+    Self: Sized + Instance + WithPwm,
+{
+    fn output_compare<PINS, const FREQ: u32>(
+        self,
+        pins: PINS,
+        clocks: &Clocks,
+    ) -> OutputCompare<Self, PINS, FREQ>
+    where
+        PINS: Pins<Self>,
+    {
+        FTimer::<Self, FREQ>::new(self, clocks).output_compare(pins)
+    }
+}
+
+/// Free-running timer with scheduled channel events
+pub struct OutputCompare<TMR, PINS, const FREQ: u32>
+where
+    TMR: Instance + WithPwm,
+    PINS: Pins<TMR>,
+{
+    timer: FTimer<TMR, FREQ>,
+    _pins: PhantomData<PINS>,
+}
+
+impl<TMR: Instance + WithPwm, const FREQ: u32> FTimer<TMR, FREQ> {
+    pub fn output_compare<PINS>(mut self, _pins: PINS) -> OutputCompare<TMR, PINS, FREQ>
+    where
+        PINS: Pins<TMR>,
+    {
+        self.tmr.set_auto_reload(TMR::max_auto_reload()).unwrap();
+
+        // Channel values are not buffered, so a new event is armed right away
+        for c in 0..TMR::CH_NUMBER {
+            TMR::set_output_mode(c, Ocm::Frozen);
+        }
+        for (c, used) in [PINS::C1, PINS::C2, PINS::C3, PINS::C4]
+            .into_iter()
+            .enumerate()
+        {
+            if used {
+                TMR::enable_channel(c as u8, true);
+            }
+        }
+        self.tmr.enable_auto_output();
+
+        self.tmr.trigger_update();
+        self.tmr.enable_counter();
+
+        OutputCompare {
+            timer: self,
+            _pins: PhantomData,
+        }
+    }
+}
+
+impl<TMR, PINS, const FREQ: u32> OutputCompare<TMR, PINS, FREQ>
+where
+    TMR: Instance + WithPwm,
+    PINS: Pins<TMR>,
+{
+    /// Current counter value
+    #[inline]
+    pub fn now(&self) -> u32 {
+        self.tmr.read_count().into()
+    }
+
+    /// Schedule `action` on `channel` when the counter reaches `at`
+    ///
+    /// `at` wraps at the end of the counter range. Actions other than
+    /// [`CompareAction::Interrupt`] need a pin on the channel.
+    pub fn schedule(&mut self, channel: Channel, at: u32, action: CompareAction) {
+        let c = if action == CompareAction::Interrupt {
+            assert!((channel as u8) < TMR::CH_NUMBER);
+            channel
+        } else {
+            PINS::check_used(channel)
+        } as u8;
+        // Stop the previous event first, so it can't fire on the new value
+        TMR::set_output_mode(c, Ocm::Frozen);
+        // The full 32-bit range needs no wrapping
+        let at = at
+            .checked_rem(TMR::read_auto_reload().wrapping_add(1))
+            .unwrap_or(at);
+        TMR::set_cc_value(c, at);
+        self.tmr.clear_interrupt_flag(channel_event(channel));
+        TMR::set_output_mode(c, action.into());
+    }
+
+    /// Schedule `action` on `channel` `delay` after now
+    pub fn schedule_in(
+        &mut self,
+        channel: Channel,
+        delay: TimerDurationU32<FREQ>,
+        action: CompareAction,
+    ) {
+        let at = self.now().wrapping_add(delay.ticks());
+        self.schedule(channel, at, action);
+    }
+
+    /// Cancel the event scheduled on `channel`, the output keeps its level
+    pub fn cancel(&mut self, channel: Channel) {
+        TMR::set_output_mode(channel as u8, Ocm::Frozen);
+    }
+
+    /// Drive the output of `channel` to its active or inactive level right away
+    ///
+    /// Cancels the event scheduled on the channel.
+    pub fn force(&mut self, channel: Channel, active: bool) {
+        let mode = if active {
+            Ocm::ForceActive
+        } else {
+            Ocm::ForceInactive
+        };
+        TMR::set_output_mode(PINS::check_used(channel) as u8, mode);
+    }
+
+    /// Tick of the event scheduled last on `channel`
+    pub fn scheduled_at(&self, channel: Channel) -> u32 {
+        TMR::read_cc_value(channel as u8)
+    }
+
+    /// Returns `true` if the counter matched the tick scheduled on `channel`
+    pub fn is_matched(&self, channel: Channel) -> bool {
+        self.tmr
+            .get_interrupt_flag()
+            .contains(channel_event(channel))
+    }
+
+    /// Handle matched channels, to be called from the timer interrupt
+    ///
+    /// `f` gets every matched channel with its scheduled tick. Returning the next tick and
+    /// action rearms the channel, `None` cancels it.
+    pub fn on_match(&mut self, mut f: impl FnMut(Channel, u32) -> Option<(u32, CompareAction)>) {
+        let flags = self.tmr.get_interrupt_flag();
+        for channel in [Channel::C1, Channel::C2, Channel::C3, Channel::C4]
+            .into_iter()
+            .take(TMR::CH_NUMBER as usize)
+        {
+            if !flags.contains(channel_event(channel)) {
+                continue;
+            }
+            self.tmr.clear_interrupt_flag(channel_event(channel));
+            let at = TMR::read_cc_value(channel as u8);
+            match f(channel, at) {
+                Some((next, action)) => self.schedule(channel, next, action),
+                None => self.cancel(channel),
+            }
+        }
+    }
+
+    pub fn release(mut self) -> FTimer<TMR, FREQ> {
+        // stop counter
+        self.tmr.ctrl1_reset();
+        self.timer
+    }
+}
+
+impl<TMR, PINS, const FREQ: u32> Deref for OutputCompare<TMR, PINS, FREQ>
+where
+    TMR: Instance + WithPwm,
+    PINS: Pins<TMR>,
+{
+    type Target = FTimer<TMR, FREQ>;
+    fn deref(&self) -> &Self::Target {
+        &self.timer
+    }
+}
+
+impl<TMR, PINS, const FREQ: u32> DerefMut for OutputCompare<TMR, PINS, FREQ>
+where
+    TMR: Instance + WithPwm,
+    PINS: Pins<TMR>,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.timer
+    }
+}
+
+const fn channel_event(channel: Channel) -> Event {
+    match channel {
+        Channel::C1 => Event::C1,
+        Channel::C2 => Event::C2,
+        Channel::C3 => Event::C3,
+        Channel::C4 => Event::C4,
+    }
+}
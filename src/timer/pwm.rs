@@ -35,13 +35,20 @@
 //!
 //! After creating structures you can dynamically enable main or complementary channels with `enable` and `enable_complementary`
 //! and change their polarity with `set_polarity` and `set_complementary_polarity`.
+//!
+//! PWM starts edge-aligned and counting up. Timers with 4 channels can be switched to down-counting or to one of the
+//! center-aligned modes with `set_count_mode`, the PWM frequency is kept. In center-aligned modes the pulses of all
+//! channels are centered on the counter underflow, a channel in [`PwmMode::Mode2`] is centered on the overflow instead,
+//! which gives two pulse trains shifted by half a period. The timers have no hardware mode for pulses at any other
+//! position, `emulate_asymmetric_duty` emulates it by rewriting the channel data register on every update event.
 
 use super::{
-    compute_arr_presc, Advanced, CPin, Channel, FTimer, IdleState, Instance, Ocm, Polarity, Timer,
-    WithPwm,
+    compute_arr_presc, Advanced, CPin, Channel, CountMode, DmaEvent, FTimer, IdleState, Instance,
+    Ocm, Polarity, Timer, WithCountMode, WithDmar, WithPwm, CCR,
 };
 pub use super::{Ch, C1, C2, C3, C4};
 use crate::crm::Clocks;
+use crate::dma::{self, MemoryToPeripheral, Transfer};
 use crate::gpio::PushPull;
 use core::marker::PhantomData;
 use core::ops::{Deref, DerefMut};
@@ -176,6 +183,25 @@ pins_impl!(
                                                  (O4, C4, NC4);
 );
 
+/// PWM output mode of a channel
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PwmMode {
+    /// Output is active while the counter is below the duty cycle value
+    Mode1,
+    /// Output is active while the counter is at or above the duty cycle value
+    Mode2,
+}
+
+impl From<PwmMode> for Ocm {
+    fn from(mode: PwmMode) -> Self {
+        match mode {
+            PwmMode::Mode1 => Ocm::PwmMode1,
+            PwmMode::Mode2 => Ocm::PwmMode2,
+        }
+    }
+}
+
 pub struct PwmChannel<TMR, const C: u8, const COMP: bool = false> {
     pub(super) _tmr: PhantomData<TMR>,
 }
@@ -272,7 +298,7 @@ impl<TMR: Instance + WithPwm, const C: u8, const COMP: bool> PwmChannel<TMR, C,
     /// If `0` returned means max_duty is 2^16
     #[inline]
     pub fn get_max_duty(&self) -> u16 {
-        (TMR::read_auto_reload() as u16).wrapping_add(1)
+        max_duty::<TMR>()
     }
 
     /// Set PWM channel duty cycle
@@ -286,6 +312,45 @@ impl<TMR: Instance + WithPwm, const C: u8, const COMP: bool> PwmChannel<TMR, C,
     pub fn set_complementary_polarity(&mut self, p: Polarity) {
         TMR::set_nchannel_polarity(C, p);
     }
+
+    /// Set PWM channel output mode
+    #[inline]
+    pub fn set_pwm_mode(&mut self, mode: PwmMode) {
+        TMR::set_output_mode(C, mode.into());
+    }
+}
+
+impl<TMR: Instance + WithPwm + WithCountMode, const C: u8, const COMP: bool>
+    PwmChannel<TMR, C, COMP>
+{
+    /// Emulate asymmetric PWM by software
+    ///
+    /// See [`PwmHz::emulate_asymmetric_duty`].
+    pub fn emulate_asymmetric_duty(&mut self, leading: u16, trailing: u16) {
+        emulate_asymmetric_duty::<TMR>(C, leading, trailing);
+    }
+}
+
+impl<TMR: Instance + WithPwm + WithDmar, const C: u8, const COMP: bool> PwmChannel<TMR, C, COMP> {
+    /// Play `duties` on the channel, loading the next duty cycle on every update event
+    ///
+    /// The returned transfer must be started. If `circular` the buffer is repeated until the
+    /// transfer is released, otherwise the last value stays active.
+    /// On devices with fixed DMA mapping `dma_channel` must be the channel of the timer update request.
+    pub fn play_duty_buffer<DMA: dma::traits::Instance, const N: u8>(
+        &mut self,
+        dma_channel: dma::Channel<DMA, N>,
+        duties: &'static [u16],
+        circular: bool,
+    ) -> Transfer<dma::Channel<DMA, N>, CCR<TMR, C>, &'static [u16], MemoryToPeripheral> {
+        TMR::enable_dma_request(DmaEvent::Update, true);
+        Transfer::init_memory_to_peripheral(dma_channel, CCR::new(), duties, circular)
+    }
+
+    /// Stop issuing DMA requests for the duty cycle buffer
+    pub fn stop_duty_buffer(&mut self) {
+        TMR::enable_dma_request(DmaEvent::Update, false);
+    }
 }
 
 impl<TMR: Instance + WithPwm + Advanced, const C: u8> PwmChannel<TMR, C, true> {
@@ -442,7 +507,7 @@ where
     ///
     /// If `0` returned means max_duty is 2^16
     pub fn get_max_duty(&self) -> u16 {
-        (TMR::read_auto_reload() as u16).wrapping_add(1)
+        max_duty::<TMR>()
     }
 
     /// Get the PWM frequency of the timer in Hertz
@@ -452,16 +517,29 @@ where
         let arr = TMR::read_auto_reload();
 
         // Length in ms of an internal clock pulse
-        clk / ((psc + 1) * (arr + 1))
+        if TMR::read_count_mode().is_center_aligned() {
+            // ARR is 0 before the first `set_period`, which stops the counter
+            clk / ((psc + 1) * 2 * arr.max(1))
+        } else {
+            clk / ((psc + 1) * (arr + 1))
+        }
     }
 
     /// Set the PWM frequency for the timer in Hertz
     pub fn set_period(&mut self, period: Hertz) {
         let clk = self.clk;
 
-        let (psc, arr) = compute_arr_presc(period.raw(), clk.raw());
-        self.tmr.set_prescaler(psc);
-        self.tmr.set_auto_reload(arr).unwrap();
+        if TMR::read_count_mode().is_center_aligned() {
+            // The counter needs 2 * ARR ticks for a period
+            let (psc, arr) = compute_arr_presc(period.raw() * 2, clk.raw());
+            self.tmr.set_prescaler(psc);
+            let arr = (arr + 1).min(TMR::max_auto_reload());
+            self.tmr.set_auto_reload(arr).unwrap();
+        } else {
+            let (psc, arr) = compute_arr_presc(period.raw(), clk.raw());
+            self.tmr.set_prescaler(psc);
+            self.tmr.set_auto_reload(arr).unwrap();
+        }
         self.tmr.cnt_reset();
     }
 
@@ -470,6 +548,56 @@ where
     pub fn set_complementary_polarity(&mut self, channel: Channel, p: Polarity) {
         TMR::set_channel_polarity(PINS::check_complementary_used(channel) as u8, p);
     }
+
+    /// Set the output mode of the timer on channel `channel`
+    #[inline]
+    pub fn set_pwm_mode(&mut self, channel: Channel, mode: PwmMode) {
+        TMR::set_output_mode(PINS::check_used(channel) as u8, mode.into());
+    }
+}
+
+impl<TMR, PINS> PwmHz<TMR, PINS>
+where
+    TMR: Instance + WithPwm + WithCountMode,
+    PINS: Pins<TMR>,
+{
+    /// Change the counting mode and keep the PWM frequency
+    ///
+    /// The maximum duty cycle changes, so duty cycles should be set again.
+    /// A stopped counter stays stopped.
+    pub fn set_count_mode(&mut self, mode: CountMode) {
+        let period = self.get_period();
+        // The alignment can only be changed while the counter is stopped
+        let enabled = self.tmr.is_counter_enabled();
+        self.tmr.disable_counter();
+        self.tmr.set_count_mode(mode);
+        self.set_period(period);
+        self.tmr.trigger_update();
+        if enabled {
+            self.tmr.enable_counter();
+        }
+    }
+
+    /// Get the counting mode of the timer
+    pub fn get_count_mode(&self) -> CountMode {
+        TMR::read_count_mode()
+    }
+
+    /// Emulate asymmetric PWM on channel `channel` by software
+    ///
+    /// In a center-aligned mode and [`PwmMode::Mode1`] the pulse starts `leading` ticks before
+    /// the counter underflow and ends `trailing` ticks after it, so unequal values shift the
+    /// pulse within the period. The timers have no hardware mode for this: the channel data
+    /// register is rewritten for every half period and the written value is loaded at the next
+    /// update event.
+    ///
+    /// Call this on every update event, e.g. from the update interrupt, with the repetition
+    /// counter at zero. The call must finish within the half period that follows the update
+    /// event, i.e. within auto-reload ticks. If it is late or missed, the previous half period
+    /// is repeated, which gives a symmetric pulse with the wrong width for that period.
+    pub fn emulate_asymmetric_duty(&mut self, channel: Channel, leading: u16, trailing: u16) {
+        emulate_asymmetric_duty::<TMR>(PINS::check_used(channel) as u8, leading, trailing);
+    }
 }
 
 impl<TMR, PINS> PwmHz<TMR, PINS>
@@ -671,17 +799,29 @@ where
     ///
     /// If `0` returned means max_duty is 2^16
     pub fn get_max_duty(&self) -> u16 {
-        (TMR::read_auto_reload() as u16).wrapping_add(1)
+        max_duty::<TMR>()
     }
 
     /// Get the PWM frequency of the timer as a duration
     pub fn get_period(&self) -> TimerDurationU32<FREQ> {
-        TimerDurationU32::from_ticks(TMR::read_auto_reload() + 1)
+        let arr = TMR::read_auto_reload();
+        if TMR::read_count_mode().is_center_aligned() {
+            TimerDurationU32::from_ticks(2 * arr)
+        } else {
+            TimerDurationU32::from_ticks(arr + 1)
+        }
     }
 
     /// Set the PWM frequency for the timer from a duration
+    ///
+    /// In center-aligned modes the period is rounded down to an even number of ticks.
     pub fn set_period(&mut self, period: TimerDurationU32<FREQ>) {
-        self.tmr.set_auto_reload(period.ticks() - 1).unwrap();
+        let arr = if TMR::read_count_mode().is_center_aligned() {
+            period.ticks() / 2
+        } else {
+            period.ticks() - 1
+        };
+        self.tmr.set_auto_reload(arr).unwrap();
         self.tmr.cnt_reset();
     }
 
@@ -690,11 +830,72 @@ where
     pub fn set_complementary_polarity(&mut self, channel: Channel, p: Polarity) {
         TMR::set_channel_polarity(PINS::check_complementary_used(channel) as u8, p);
     }
+
+    /// Set the output mode of the timer on channel `channel`
+    #[inline]
+    pub fn set_pwm_mode(&mut self, channel: Channel, mode: PwmMode) {
+        TMR::set_output_mode(PINS::check_used(channel) as u8, mode.into());
+    }
+}
+
+impl<TMR, PINS, const FREQ: u32> Pwm<TMR, PINS, FREQ>
+where
+    TMR: Instance + WithPwm + WithCountMode,
+    PINS: Pins<TMR>,
+{
+    /// Change the counting mode and keep the PWM period
+    ///
+    /// The maximum duty cycle changes, so duty cycles should be set again.
+    /// A stopped counter stays stopped.
+    pub fn set_count_mode(&mut self, mode: CountMode) {
+        let period = self.get_period();
+        // The alignment can only be changed while the counter is stopped
+        let enabled = self.tmr.is_counter_enabled();
+        self.tmr.disable_counter();
+        self.tmr.set_count_mode(mode);
+        self.set_period(period);
+        self.tmr.trigger_update();
+        if enabled {
+            self.tmr.enable_counter();
+        }
+    }
+
+    /// Get the counting mode of the timer
+    pub fn get_count_mode(&self) -> CountMode {
+        TMR::read_count_mode()
+    }
+
+    /// Emulate asymmetric PWM on channel `channel` by software
+    ///
+    /// See [`PwmHz::emulate_asymmetric_duty`].
+    pub fn emulate_asymmetric_duty(&mut self, channel: Channel, leading: u16, trailing: u16) {
+        emulate_asymmetric_duty::<TMR>(PINS::check_used(channel) as u8, leading, trailing);
+    }
+}
+
+/// Maximum duty cycle for the current auto-reload value and counting mode
+fn max_duty<TMR: WithPwm>() -> u16 {
+    let arr = TMR::read_auto_reload() as u16;
+    if TMR::read_count_mode().is_center_aligned() {
+        arr
+    } else {
+        arr.wrapping_add(1)
+    }
+}
+
+fn emulate_asymmetric_duty<TMR: WithCountMode + WithPwm>(c: u8, leading: u16, trailing: u16) {
+    // The value written now is loaded at the next update, where the direction changes
+    let duty = if TMR::is_counting_down() {
+        trailing
+    } else {
+        leading
+    };
+    TMR::set_cc_value(c, duty as u32);
 }
 
 /// Convert number dead time ticks to raw DTG register bits.
 /// Values greater than 1009 result in maximum dead time of 126 us
-const fn pack_ceil_dead_time(dts_ticks: u16) -> u8 {
+pub(super) const fn pack_ceil_dead_time(dts_ticks: u16) -> u8 {
     match dts_ticks {
         0..=127 => dts_ticks as u8,
         128..=254 => ((((dts_ticks + 1) >> 1) - 64) as u8) | 0b_1000_0000,
@@ -705,7 +906,7 @@ const fn pack_ceil_dead_time(dts_ticks: u16) -> u8 {
 }
 
 /// Convert raw DTG register bits value to number of dead time ticks
-const fn unpack_dead_time(bits: u8) -> u16 {
+pub(super) const fn unpack_dead_time(bits: u8) -> u16 {
     if bits & 0b_1000_0000 == 0 {
         bits as u16
     } else if bits & 0b_0100_0000 == 0 {
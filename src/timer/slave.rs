@@ -0,0 +1,211 @@
+//! Slave mode controller
+//!
+//! A timer in slave mode is reset, gated, started or clocked by a trigger signal.
+//! The trigger comes from another timer (`Itr0`-`Itr3`, see the internal trigger
+//! connection table in the reference manual), from the channel 1/2 inputs or from the
+//! EXT pin. Together with [`set_master_mode`](super::Timer::set_master_mode) on the
+//! other timer this chains timers into longer counters or gates PWM bursts.
+//!
+//! ```rust
+//! // TMR3 counts the update events of TMR2, TMR2 master mode is set to update
+//! tmr3.set_slave_mode(SlaveMode::ExternalClock1, TriggerSource::Itr1);
+//!
+//! // TMR4 counts rising edges on its EXT pin
+//! let ext = tmr4.set_external_clock(gpioe.pe0, ExternalTrigger::new());
+//! ```
+
+use super::{
+    CPin, CaptureEdge, CaptureInput, CapturePrescaler, CaptureSource, FTimer, Instance, Timer,
+    WithCapture, WithSlave,
+};
+use crate::gpio::alt::TmrExt;
+
+/// Slave mode selection (SMSEL)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum SlaveMode {
+    /// Counter is clocked by the internal clock
+    Disabled = 0b000,
+    /// Rising edge of the trigger resets the counter
+    Reset = 0b100,
+    /// Counter runs while the trigger is high
+    Gated = 0b101,
+    /// Rising edge of the trigger starts the counter
+    Trigger = 0b110,
+    /// Rising edges of the trigger clock the counter
+    ExternalClock1 = 0b111,
+}
+
+/// Trigger selection (STIS)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum TriggerSource {
+    /// Internal trigger 0
+    Itr0 = 0b000,
+    /// Internal trigger 1
+    Itr1 = 0b001,
+    /// Internal trigger 2
+    Itr2 = 0b010,
+    /// Internal trigger 3
+    Itr3 = 0b011,
+    /// Both edges of channel 1 input
+    Ti1Edge = 0b100,
+    /// Filtered channel 1 input
+    Ti1 = 0b101,
+    /// Filtered channel 2 input
+    Ti2 = 0b110,
+    /// Filtered EXT pin input
+    Ext = 0b111,
+}
+
+/// EXT pin of `TMR`, connected with `set_external_clock`
+pub struct ExtPin<TMR: TmrExt> {
+    pin: TMR::Ext,
+}
+
+impl<TMR: TmrExt> ExtPin<TMR> {
+    /// Get the pin back, e.g. to convert it into another mode with `TryFrom`
+    pub fn release(self) -> TMR::Ext {
+        self.pin
+    }
+}
+
+/// Division of the EXT input frequency
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum ExternalPrescaler {
+    Div1 = 0,
+    Div2 = 1,
+    Div4 = 2,
+    Div8 = 3,
+}
+
+/// EXT input configuration
+///
+/// After the prescaler the EXT signal must stay below a quarter of the timer clock.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ExternalTrigger {
+    /// Digital filter, `0` disables filtering
+    pub filter: u8,
+    pub prescaler: ExternalPrescaler,
+    /// Count falling instead of rising edges
+    pub inverted: bool,
+}
+
+impl Default for ExternalTrigger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExternalTrigger {
+    pub const fn new() -> Self {
+        Self {
+            filter: 0,
+            prescaler: ExternalPrescaler::Div1,
+            inverted: false,
+        }
+    }
+
+    pub const fn filter(mut self, filter: u8) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub const fn prescaler(mut self, prescaler: ExternalPrescaler) -> Self {
+        self.prescaler = prescaler;
+        self
+    }
+
+    pub const fn inverted(mut self, inverted: bool) -> Self {
+        self.inverted = inverted;
+        self
+    }
+}
+
+macro_rules! slave_impl {
+    () => {
+        /// Select the slave mode and its trigger
+        pub fn set_slave_mode(&mut self, mode: SlaveMode, trigger: TriggerSource) {
+            self.tmr.set_slave_mode(mode, trigger);
+        }
+
+        /// Clock the counter from the internal clock again
+        pub fn disable_slave_mode(&mut self) {
+            self.tmr
+                .set_slave_mode(SlaveMode::Disabled, TriggerSource::Itr0);
+            self.tmr.set_external_trigger(ExternalTrigger::new(), false);
+        }
+
+        /// Configure the EXT input, used with [`TriggerSource::Ext`]
+        pub fn set_external_trigger(&mut self, config: ExternalTrigger) {
+            self.tmr.set_external_trigger(config, false);
+        }
+
+        /// Clock the counter from the EXT pin (external clock mode 2)
+        ///
+        /// Unlike [`SlaveMode::ExternalClock1`] with [`TriggerSource::Ext`], this keeps
+        /// the slave mode controller free for reset, gated or trigger mode. The pin is
+        /// switched to the input mode of its alternate function.
+        pub fn set_external_clock(
+            &mut self,
+            pin: impl Into<TMR::Ext>,
+            config: ExternalTrigger,
+        ) -> ExtPin<TMR>
+        where
+            TMR: TmrExt,
+        {
+            let pin = pin.into();
+            self.tmr.set_external_trigger(config, true);
+            ExtPin { pin }
+        }
+
+        /// Delay the trigger input so master and slave timers start at the same time
+        pub fn set_master_slave_sync(&mut self, enable: bool) {
+            self.tmr.set_master_slave_sync(enable);
+        }
+    };
+}
+
+macro_rules! trigger_input_impl {
+    () => {
+        /// Use channel 1 or 2 input as trigger with polarity `edge` and digital `filter`
+        ///
+        /// Select it with [`TriggerSource::Ti1`] or [`TriggerSource::Ti2`].
+        /// `CaptureEdge::Both` is only supported with [`TriggerSource::Ti1Edge`].
+        /// The input is only borrowed, keep it as long as the trigger is used.
+        pub fn trigger_input<const C: u8>(
+            &mut self,
+            _input: &CaptureInput<TMR, C>,
+            edge: CaptureEdge,
+            filter: u8,
+        ) where
+            TMR: CPin<C>,
+        {
+            assert!(C < 2);
+            TMR::enable_channel(C, false);
+            TMR::set_input_mode(C, CaptureSource::Direct, filter, CapturePrescaler::Div1);
+            TMR::set_capture_edge(C, edge);
+        }
+    };
+}
+
+impl<TMR: Instance + WithSlave> Timer<TMR> {
+    slave_impl!();
+}
+
+impl<TMR: Instance + WithSlave, const FREQ: u32> FTimer<TMR, FREQ> {
+    slave_impl!();
+}
+
+impl<TMR: Instance + WithSlave + WithCapture> Timer<TMR> {
+    trigger_input_impl!();
+}
+
+impl<TMR: Instance + WithSlave + WithCapture, const FREQ: u32> FTimer<TMR, FREQ> {
+    trigger_input_impl!();
+}
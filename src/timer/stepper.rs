@@ -0,0 +1,380 @@
+//! Step pulse generator for step/dir stepper drivers
+//!
+//! [`Stepper`] outputs one pulse of fixed width per timer period on a channel of an advanced
+//! timer (TMR1, TMR8, TMR20). The counter counts down, so every pulse sits at the end of its
+//! period and the output stays inactive when the counter stops. The counter stops by itself
+//! after the last step of a move, so the number of steps is exact.
+//!
+//! - [`run`](Stepper::run) moves at a constant rate. The repetition counter raises the update
+//!   event only once per burst of up to 256 steps.
+//! - [`move_profile`](Stepper::move_profile) follows a [`Trapezoid`] profile. The periods of
+//!   all steps are computed into the DMA buffer and loaded into the auto-reload register on
+//!   every update event, without interrupts per step.
+//!
+//! ```rust
+//! static mut PERIODS: [u16; 4000] = [0; 4000];
+//!
+//! let dma1 = dp.DMA1.split();
+//! let mut stepper = dp.TMR1.stepper_us(
+//!     Channel1::new(gpioa.pa8),
+//!     dma1.5,
+//!     unsafe { &mut *core::ptr::addr_of_mut!(PERIODS) },
+//!     2.micros(),
+//!     &clocks,
+//! );
+//! stepper.move_profile(&Trapezoid::new(200, 5_000, 20_000), 3200).unwrap();
+//!
+//! // in the DMA channel interrupt
+//! stepper.on_dma_complete();
+//! // in the TMR1 update interrupt
+//! stepper.on_update(|steps| rprintln!("moved {} steps", steps));
+//! ```
+//!
+//! The direction pin is a plain GPIO output, set it while no move is in progress.
+
+pub mod profile;
+pub use profile::Trapezoid;
+
+use super::{
+    Advanced, ChannelBuilder, CountMode, DmaBurstBase, DmaEvent, Error, Event, FTimer, Instance,
+    Ocm, WithCountMode, WithDmar, WithPwm, DMAR,
+};
+use crate::crm::Clocks;
+use crate::dma::{self, traits::PeriAddress, MemoryToPeripheral};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use fugit::TimerDurationU32;
+
+/// Steps per burst of the 8-bit repetition counter
+const MAX_BURST: u32 = 256;
+
+pub trait StepperExt
+where
+    Self: Sized + Instance + WithPwm + Advanced + WithCountMode + WithDmar,
+    DMAR<Self>: PeriAddress<MemSize = u16>,
+{
+    fn stepper<const C: u8, const COMP: bool, Otype, DMA, const N: u8, const FREQ: u32>(
+        self,
+        channel: ChannelBuilder<Self, C, COMP, Otype>,
+        dma_channel: dma::Channel<DMA, N>,
+        buf: &'static mut [u16],
+        pulse_width: TimerDurationU32<FREQ>,
+        clocks: &Clocks,
+    ) -> Stepper<Self, DMA, C, N, FREQ>
+    where
+        DMA: dma::traits::Instance;
+
+    fn stepper_us<const C: u8, const COMP: bool, Otype, DMA, const N: u8>(
+        self,
+        channel: ChannelBuilder<Self, C, COMP, Otype>,
+        dma_channel: dma::Channel<DMA, N>,
+        buf: &'static mut [u16],
+        pulse_width: TimerDurationU32<1_000_000>,
+        clocks: &Clocks,
+    ) -> Stepper<Self, DMA, C, N, 1_000_000>
+    where
+        DMA: dma::traits::Instance,
+    {
+        self.stepper(channel, dma_channel, buf, pulse_width, clocks)
+    }
+}
+
+impl<TMR> StepperExt for TMR
+where
+    Self: Sized + Instance + WithPwm + Advanced + WithCountMode + WithDmar,
+    DMAR<Self>: PeriAddress<MemSize = u16>,
+{
+    fn stepper<const C: u8, const COMP: bool, Otype, DMA, const N: u8, const FREQ: u32>(
+        self,
+        channel: ChannelBuilder<Self, C, COMP, Otype>,
+        dma_channel: dma::Channel<DMA, N>,
+        buf: &'static mut [u16],
+        pulse_width: TimerDurationU32<FREQ>,
+        clocks: &Clocks,
+    ) -> Stepper<Self, DMA, C, N, FREQ>
+    where
+        DMA: dma::traits::Instance,
+    {
+        FTimer::<Self, FREQ>::new(self, clocks).stepper(channel, dma_channel, buf, pulse_width)
+    }
+}
+
+/// Step pulse generator on channel `C`
+pub struct Stepper<TMR, DMA, const C: u8, const N: u8, const FREQ: u32>
+where
+    TMR: Instance + WithPwm + Advanced + WithCountMode + WithDmar,
+    DMA: dma::traits::Instance,
+{
+    timer: FTimer<TMR, FREQ>,
+    dma: dma::Channel<DMA, N>,
+    buf: &'static mut [u16],
+    /// Steps of the current move
+    steps: u32,
+    /// Steps of a constant rate move not loaded into the repetition counter yet
+    unloaded: u32,
+    /// Steps of the burst waiting in the repetition counter buffer
+    next_burst: u32,
+    /// The counter stops at the next update event
+    stopping: bool,
+    moving: bool,
+    total: u64,
+}
+
+impl<TMR, const FREQ: u32> FTimer<TMR, FREQ>
+where
+    TMR: Instance + WithPwm + Advanced + WithCountMode + WithDmar,
+    DMAR<TMR>: PeriAddress<MemSize = u16>,
+{
+    pub fn stepper<const C: u8, const COMP: bool, Otype, DMA, const N: u8>(
+        mut self,
+        _channel: ChannelBuilder<TMR, C, COMP, Otype>,
+        dma_channel: dma::Channel<DMA, N>,
+        buf: &'static mut [u16],
+        pulse_width: TimerDurationU32<FREQ>,
+    ) -> Stepper<TMR, DMA, C, N, FREQ>
+    where
+        DMA: dma::traits::Instance,
+    {
+        self.tmr.set_count_mode(CountMode::Down);
+        // The output is active while the counter is below the pulse width
+        TMR::set_output_mode(C, Ocm::PwmMode1);
+        TMR::set_cc_value(C, pulse_width.ticks().max(1));
+        // Periods written by the DMA are used from the next update event on
+        self.tmr.enable_preload(true);
+        // Every update event writes the next period to PR
+        self.tmr.set_dma_burst(DmaBurstBase::Pr, 1);
+        self.tmr.enable_auto_output();
+        TMR::enable_channel(C, true);
+
+        Stepper {
+            timer: self,
+            dma: dma_channel,
+            buf,
+            steps: 0,
+            unloaded: 0,
+            next_burst: 0,
+            stopping: false,
+            moving: false,
+            total: 0,
+        }
+    }
+}
+
+impl<TMR, DMA, const C: u8, const N: u8, const FREQ: u32> Stepper<TMR, DMA, C, N, FREQ>
+where
+    TMR: Instance + WithPwm + Advanced + WithCountMode + WithDmar,
+    DMA: dma::traits::Instance,
+    DMAR<TMR>: PeriAddress<MemSize = u16>,
+{
+    /// Output `steps` steps with a period of `period`
+    ///
+    /// Returns an error if a move is in progress or the period does not fit into the counter.
+    pub fn run(&mut self, steps: u32, period: TimerDurationU32<FREQ>) -> Result<(), Error> {
+        if self.moving {
+            return Err(Error::Busy);
+        }
+        if steps == 0 {
+            return Ok(());
+        }
+        self.set_period(period.ticks())?;
+        self.begin(steps);
+
+        let first = steps.min(MAX_BURST);
+        self.tmr.set_repetition_counter((first - 1) as u8);
+        self.tmr.trigger_update();
+        self.unloaded = steps - first;
+        // The repetition counter value is buffered, so the next burst is loaded already
+        self.load_next_burst();
+
+        // One interrupt per burst
+        self.tmr.listen_interrupt(Event::Update, true);
+        self.tmr.enable_counter();
+        Ok(())
+    }
+
+    /// Output `steps` steps following `profile`
+    ///
+    /// Returns an error if a move is in progress or the slowest period does not fit into
+    /// the counter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the DMA buffer is shorter than `steps - 1` or `steps` exceeds 65536.
+    pub fn move_profile(&mut self, profile: &Trapezoid, steps: u32) -> Result<(), Error> {
+        if self.moving {
+            return Err(Error::Busy);
+        }
+        if steps < 2 {
+            let period = TimerDurationU32::from_ticks(FREQ / profile.start_rate());
+            return self.run(steps, period);
+        }
+        assert!(self.buf.len() >= (steps - 1) as usize && steps <= u16::MAX as u32 + 1);
+
+        let mut periods = profile.periods(steps, FREQ);
+        // The slowest steps are the first and the last one
+        self.set_period(periods.next().unwrap())?;
+        self.begin(steps);
+        self.tmr.set_repetition_counter(0);
+        self.tmr.trigger_update();
+        // Buffered until the end of the first step
+        self.set_period(periods.next().unwrap())?;
+
+        // The DMA writes one period on every update event, starting at the end of the
+        // first step with the period of the third one. The last write only serves as
+        // marker: its transfer complete interrupt arrives during the last step.
+        let len = (steps - 1) as usize;
+        for (slot, period) in self.buf[..len - 1].iter_mut().zip(periods) {
+            *slot = (period - 1) as u16;
+        }
+        self.buf[len - 1] = TMR::read_auto_reload() as u16;
+        unsafe {
+            self.dma.configure::<DMAR<TMR>, MemoryToPeripheral>(
+                DMAR::<TMR>(PhantomData).address(),
+                self.buf.as_ptr() as u32,
+                len as u16,
+                false,
+            );
+        }
+        self.dma.listen(dma::Event::TransferComplete);
+        self.dma.enable();
+        TMR::enable_dma_request(DmaEvent::Update, true);
+
+        self.tmr.enable_counter();
+        Ok(())
+    }
+
+    /// Stop the move at the end of the current step, or burst of [`run`](Self::run)
+    ///
+    /// The completion reports the steps that were actually output.
+    pub fn stop(&mut self) {
+        if !self.moving || self.stopping {
+            return;
+        }
+        if self.dma.is_enabled() {
+            // Every update event so far wrote one period, the current step is the next one
+            TMR::enable_dma_request(DmaEvent::Update, false);
+            let written = self.steps - 1 - self.dma.remaining() as u32;
+            self.steps = written + 1;
+        } else {
+            self.steps -= self.unloaded + self.next_burst;
+            self.unloaded = 0;
+            self.next_burst = 0;
+        }
+        self.finish_loading();
+    }
+
+    /// Handle the transfer complete interrupt of the DMA channel
+    pub fn on_dma_complete(&mut self) {
+        if self.dma.events().contains(dma::Event::TransferComplete) {
+            self.dma.clear_events(dma::Event::all());
+            self.finish_loading();
+        }
+    }
+
+    /// Handle the update interrupt of the timer
+    ///
+    /// The stepper enables the update interrupt itself when it is needed.
+    /// `on_complete` is called with the number of steps once a move is done.
+    pub fn on_update(&mut self, on_complete: impl FnOnce(u32)) {
+        if !self.tmr.get_interrupt_flag().contains(Event::Update) {
+            return;
+        }
+        self.tmr.clear_interrupt_flag(Event::Update);
+        if !self.moving {
+            return;
+        }
+        if self.stopping && !self.tmr.is_counter_enabled() {
+            self.moving = false;
+            self.stopping = false;
+            TMR::enable_dma_request(DmaEvent::Update, false);
+            self.dma.disable();
+            self.total += self.steps as u64;
+            on_complete(self.steps);
+        } else if !self.stopping {
+            // The buffered burst is running now
+            self.next_burst = 0;
+            self.load_next_burst();
+        }
+    }
+
+    /// Returns `true` while a move is in progress
+    pub fn is_moving(&self) -> bool {
+        self.moving
+    }
+
+    /// Number of steps of all completed moves
+    pub fn total_steps(&self) -> u64 {
+        self.total
+    }
+
+    /// Set the width of the step pulses
+    pub fn set_pulse_width(&mut self, pulse_width: TimerDurationU32<FREQ>) {
+        TMR::set_cc_value(C, pulse_width.ticks().max(1));
+    }
+
+    /// Release the timer, the DMA channel and the buffer
+    pub fn release(mut self) -> (FTimer<TMR, FREQ>, dma::Channel<DMA, N>, &'static mut [u16]) {
+        // stop counter
+        self.tmr.ctrl1_reset();
+        TMR::enable_dma_request(DmaEvent::Update, false);
+        self.dma.disable();
+        (self.timer, self.dma, self.buf)
+    }
+
+    fn set_period(&mut self, ticks: u32) -> Result<(), Error> {
+        let arr = ticks.checked_sub(1).ok_or(Error::WrongAutoReload)?;
+        self.tmr.set_auto_reload(arr)
+    }
+
+    fn begin(&mut self, steps: u32) {
+        self.tmr.listen_interrupt(Event::Update, false);
+        self.tmr.clear_interrupt_flag(Event::Update);
+        self.tmr.enable_one_pulse(false);
+        self.steps = steps;
+        self.unloaded = 0;
+        self.next_burst = 0;
+        self.stopping = false;
+        self.moving = true;
+    }
+
+    fn load_next_burst(&mut self) {
+        if self.unloaded == 0 {
+            self.finish_loading();
+        } else {
+            let burst = self.unloaded.min(MAX_BURST);
+            self.tmr.set_repetition_counter((burst - 1) as u8);
+            self.unloaded -= burst;
+            self.next_burst = burst;
+        }
+    }
+
+    /// Let the counter stop at the end of the running burst or step
+    fn finish_loading(&mut self) {
+        self.tmr.enable_one_pulse(true);
+        self.stopping = true;
+        // Profile moves only interrupt for the final update event
+        self.tmr.clear_interrupt_flag(Event::Update);
+        self.tmr.listen_interrupt(Event::Update, true);
+    }
+}
+
+impl<TMR, DMA, const C: u8, const N: u8, const FREQ: u32> Deref for Stepper<TMR, DMA, C, N, FREQ>
+where
+    TMR: Instance + WithPwm + Advanced + WithCountMode + WithDmar,
+    DMA: dma::traits::Instance,
+{
+    type Target = FTimer<TMR, FREQ>;
+    fn deref(&self) -> &Self::Target {
+        &self.timer
+    }
+}
+
+impl<TMR, DMA, const C: u8, const N: u8, const FREQ: u32> DerefMut for Stepper<TMR, DMA, C, N, FREQ>
+where
+    TMR: Instance + WithPwm + Advanced + WithCountMode + WithDmar,
+    DMA: dma::traits::Instance,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.timer
+    }
+}
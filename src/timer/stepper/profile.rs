@@ -0,0 +1,221 @@
+//! Step timing of trapezoidal moves
+//!
+//! Plain integer math without register access, so it also builds and runs on the host.
+//! The rate of every step follows `v² = v₀² + 2·a·s`, where `s` is the distance in steps
+//! to the nearer end of the move, limited to the maximum rate. Moves too short to reach
+//! the maximum rate get a triangular profile.
+
+/// Trapezoidal velocity profile
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Trapezoid {
+    start_rate: u32,
+    max_rate: u32,
+    accel: u32,
+}
+
+impl Trapezoid {
+    /// Profile starting and stopping at `start_rate` and cruising at `max_rate` steps/s,
+    /// accelerating with `accel` steps/s²
+    pub const fn new(start_rate: u32, max_rate: u32, accel: u32) -> Self {
+        assert!(start_rate > 0 && start_rate <= max_rate);
+        Self {
+            start_rate,
+            max_rate,
+            accel,
+        }
+    }
+
+    pub const fn start_rate(&self) -> u32 {
+        self.start_rate
+    }
+
+    pub const fn max_rate(&self) -> u32 {
+        self.max_rate
+    }
+
+    pub const fn accel(&self) -> u32 {
+        self.accel
+    }
+
+    /// Number of steps needed to accelerate from the start to the maximum rate
+    pub fn ramp_steps(&self) -> u32 {
+        if self.accel == 0 {
+            return 0;
+        }
+        let dv2 = sq(self.max_rate) - sq(self.start_rate);
+        dv2.div_ceil(2 * self.accel as u64) as u32
+    }
+
+    /// Rate in steps/s of step `step` (counted from 0) of a move of `steps` steps
+    pub fn rate(&self, step: u32, steps: u32) -> u32 {
+        debug_assert!(step < steps);
+        let s = step.min(steps - 1 - step);
+        let v2 = sq(self.start_rate) + 2 * self.accel as u64 * s as u64;
+        (isqrt(v2) as u32).min(self.max_rate)
+    }
+
+    /// Period of step `step` in ticks of a `freq` Hz timer
+    pub fn period(&self, step: u32, steps: u32, freq: u32) -> u32 {
+        (freq / self.rate(step, steps)).max(1)
+    }
+
+    /// Periods of all steps of a move of `steps` steps in ticks of a `freq` Hz timer
+    pub fn periods(&self, steps: u32, freq: u32) -> Periods {
+        Periods {
+            profile: *self,
+            freq,
+            step: 0,
+            steps,
+        }
+    }
+
+    /// Duration of a move of `steps` steps in ticks of a `freq` Hz timer
+    pub fn duration(&self, steps: u32, freq: u32) -> u64 {
+        self.periods(steps, freq).map(u64::from).sum()
+    }
+}
+
+/// Step periods of a move, see [`Trapezoid::periods`]
+#[derive(Clone, Debug)]
+pub struct Periods {
+    profile: Trapezoid,
+    freq: u32,
+    step: u32,
+    steps: u32,
+}
+
+impl Iterator for Periods {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.step == self.steps {
+            return None;
+        }
+        let period = self.profile.period(self.step, self.steps, self.freq);
+        self.step += 1;
+        Some(period)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.steps - self.step) as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Periods {}
+
+/// Integer square root, rounded down
+pub fn isqrt(n: u64) -> u64 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method from an initial guess above the root
+    let mut x = 1 << ((64 - n.leading_zeros()).div_ceil(2));
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+const fn sq(v: u32) -> u64 {
+    v as u64 * v as u64
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::vec::Vec;
+
+    #[test]
+    fn isqrt_rounds_down() {
+        for r in [
+            0u64,
+            1,
+            2,
+            3,
+            10,
+            255,
+            256,
+            65_535,
+            1 << 20,
+            u32::MAX as u64,
+        ] {
+            let n = r * r;
+            assert_eq!(isqrt(n), r);
+            if r > 0 {
+                assert_eq!(isqrt(n - 1), r - 1);
+            }
+            assert_eq!(isqrt(n + 1), r.max(1));
+        }
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+    }
+
+    #[test]
+    fn ramp_steps_closed_form() {
+        // (max² - start²) / (2 · accel), rounded up
+        assert_eq!(Trapezoid::new(100, 1_000, 1_000).ramp_steps(), 495);
+        assert_eq!(Trapezoid::new(200, 2_000, 5_000).ramp_steps(), 396);
+        assert_eq!(Trapezoid::new(1, 2, 1).ramp_steps(), 2);
+        assert_eq!(Trapezoid::new(500, 500, 1_000).ramp_steps(), 0);
+    }
+
+    #[test]
+    fn long_move_accelerates_cruises_and_decelerates() {
+        let profile = Trapezoid::new(100, 1_000, 1_000);
+        let ramp = profile.ramp_steps();
+        let steps = 2_000;
+        let rates: Vec<u32> = (0..steps).map(|s| profile.rate(s, steps)).collect();
+
+        assert_eq!(rates[0], 100);
+        assert_eq!(rates[steps as usize - 1], 100);
+        for s in 0..ramp {
+            let (up, down) = (s as usize, (steps - 1 - s) as usize);
+            assert!(rates[up] < 1_000);
+            assert!(rates[up] <= rates[up + 1]);
+            assert_eq!(rates[up], rates[down]);
+        }
+        for s in ramp..steps - ramp {
+            assert_eq!(rates[s as usize], 1_000);
+        }
+    }
+
+    #[test]
+    fn short_move_is_triangular_and_symmetric() {
+        let profile = Trapezoid::new(100, 1_000, 1_000);
+        let steps = 2 * profile.ramp_steps() / 3;
+        let rates: Vec<u32> = (0..steps).map(|s| profile.rate(s, steps)).collect();
+
+        assert!(rates.iter().all(|&r| r < 1_000));
+        for s in 0..steps as usize {
+            assert_eq!(rates[s], rates[steps as usize - 1 - s]);
+        }
+        let mid = (steps / 2) as usize;
+        assert!(rates[..mid].windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(rates.iter().max(), Some(&rates[mid]));
+    }
+
+    #[test]
+    fn no_acceleration_keeps_start_rate() {
+        let profile = Trapezoid::new(400, 1_000, 0);
+        assert_eq!(profile.ramp_steps(), 0);
+        assert!((0..50).all(|s| profile.rate(s, 50) == 400));
+        assert!(profile.periods(50, 1_000_000).all(|p| p == 2_500));
+        assert_eq!(profile.duration(50, 1_000_000), 50 * 2_500);
+    }
+
+    #[test]
+    fn one_period_per_step() {
+        let profile = Trapezoid::new(100, 1_000, 1_000);
+        for steps in [1, 2, 3, 100, 990, 991, 5_000] {
+            let periods = profile.periods(steps, 1_000_000);
+            assert_eq!(periods.len(), steps as usize);
+            assert_eq!(periods.count(), steps as usize);
+        }
+    }
+}
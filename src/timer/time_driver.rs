@@ -0,0 +1,188 @@
+//! Embassy time driver
+//!
+//! Implements [`embassy_time_driver::Driver`] on the timer selected with one of the
+//! `time-driver-tmrX` features. The counter runs at `embassy_time_driver::TICK_HZ` in 16-bit
+//! mode. Channel 2 interrupts at the middle and the overflow at the end of every period;
+//! counting these half periods extends the counter to 64-bit ticks. Channel 1 is the alarm.
+//!
+//! The timer interrupt must call [`on_interrupt`] and be enabled in the NVIC:
+//!
+//! ```rust
+//! time_driver::init(dp.TMR3, &clocks);
+//! unsafe { NVIC::unmask(pac::Interrupt::TMR3_GLOBAL) };
+//!
+//! #[interrupt]
+//! fn TMR3_GLOBAL() {
+//!     unsafe { time_driver::on_interrupt() };
+//! }
+//! ```
+
+use super::{FTimer, General, WithPwmCommon};
+use crate::crm::Clocks;
+use crate::pac;
+use core::cell::{Cell, RefCell};
+use core::sync::atomic::{AtomicU32, Ordering};
+use core::task::Waker;
+use cortex_m::interrupt::{self, CriticalSection, Mutex};
+use embassy_time_driver::{Driver, TICK_HZ};
+use embassy_time_queue_utils::Queue;
+
+#[cfg(feature = "time-driver-tmr2")]
+type T = pac::TMR2;
+#[cfg(feature = "time-driver-tmr3")]
+type T = pac::TMR3;
+#[cfg(feature = "time-driver-tmr4")]
+type T = pac::TMR4;
+#[cfg(feature = "time-driver-tmr5")]
+type T = pac::TMR5;
+#[cfg(feature = "time-driver-tmr9")]
+type T = pac::TMR9;
+#[cfg(feature = "time-driver-tmr12")]
+type T = pac::TMR12;
+
+// ISTS and IDEN bits
+const OVF: u32 = 1 << 0;
+const C1: u32 = 1 << 1;
+const C2: u32 = 1 << 2;
+
+// Alarms closer than 3/4 of a period are armed on channel 1, later ones
+// on one of the following half periods
+const ALARM_WINDOW: u64 = 0xc000;
+
+fn regs() -> &'static <T as core::ops::Deref>::Target {
+    unsafe { &*T::ptr() }
+}
+
+/// Start the time driver on its timer
+pub fn init(tmr: T, clocks: &Clocks) {
+    let mut timer = FTimer::<T, { TICK_HZ as u32 }>::new(tmr, clocks);
+    timer.tmr.set_auto_reload(u16::MAX as u32).unwrap();
+    // Channel 2 marks the middle of the period
+    T::set_cc_value(1, 0x8000);
+    timer.tmr.trigger_update();
+
+    let tmr = regs();
+    tmr.ists().write(|w| unsafe { w.bits(0) });
+    tmr.iden()
+        .modify(|r, w| unsafe { w.bits(r.bits() | OVF | C2) });
+    timer.tmr.enable_counter();
+    // The driver owns the timer from now on
+    core::mem::forget(timer);
+}
+
+/// Handle the timer interrupt
+///
+/// # Safety
+///
+/// Must only be called from the interrupt of the timer.
+pub unsafe fn on_interrupt() {
+    DRIVER.on_interrupt();
+}
+
+struct TmrDriver {
+    /// Number of half periods elapsed, even in the first half of a period
+    period: AtomicU32,
+    alarm: Mutex<Cell<u64>>,
+    queue: Mutex<RefCell<Queue>>,
+}
+
+embassy_time_driver::time_driver_impl!(static DRIVER: TmrDriver = TmrDriver {
+    period: AtomicU32::new(0),
+    alarm: Mutex::new(Cell::new(u64::MAX)),
+    queue: Mutex::new(RefCell::new(Queue::new())),
+});
+
+fn calc_now(period: u32, counter: u16) -> u64 {
+    ((period as u64) << 15) + ((counter as u32 ^ ((period & 1) << 15)) as u64)
+}
+
+fn listen_alarm(b: bool) {
+    regs()
+        .iden()
+        .modify(|r, w| unsafe { w.bits(if b { r.bits() | C1 } else { r.bits() & !C1 }) });
+}
+
+impl TmrDriver {
+    fn on_interrupt(&self) {
+        interrupt::free(|cs| {
+            let tmr = regs();
+            // Only handle enabled events, the alarm flag is set on every match
+            let flags = tmr.ists().read().bits() & tmr.iden().read().bits();
+            tmr.ists().write(|w| unsafe { w.bits(!flags) });
+
+            if flags & OVF != 0 {
+                self.next_period(cs);
+            }
+            if flags & C2 != 0 {
+                self.next_period(cs);
+            }
+            if flags & C1 != 0 {
+                self.trigger_alarm(cs);
+            }
+        })
+    }
+
+    fn next_period(&self, cs: &CriticalSection) {
+        let period = self.period.fetch_add(1, Ordering::Relaxed) + 1;
+        let t = (period as u64) << 15;
+
+        let at = self.alarm.borrow(cs).get();
+        if at < t + ALARM_WINDOW {
+            listen_alarm(true);
+        }
+    }
+
+    fn trigger_alarm(&self, cs: &CriticalSection) {
+        let mut queue = self.queue.borrow(cs).borrow_mut();
+        let mut next = queue.next_expiration(self.now());
+        while !self.set_alarm(cs, next) {
+            next = queue.next_expiration(self.now());
+        }
+    }
+
+    /// Arm the alarm, returns `false` if `timestamp` has already passed
+    fn set_alarm(&self, cs: &CriticalSection, timestamp: u64) -> bool {
+        self.alarm.borrow(cs).set(timestamp);
+
+        let t = self.now();
+        if timestamp <= t {
+            listen_alarm(false);
+            self.alarm.borrow(cs).set(u64::MAX);
+            return false;
+        }
+
+        T::set_cc_value(0, timestamp as u16 as u32);
+        listen_alarm(timestamp - t < ALARM_WINDOW);
+
+        // The counter may have passed the compare value while it was written
+        let t = self.now();
+        if timestamp <= t {
+            listen_alarm(false);
+            self.alarm.borrow(cs).set(u64::MAX);
+            return false;
+        }
+        true
+    }
+}
+
+impl Driver for TmrDriver {
+    fn now(&self) -> u64 {
+        let tmr = regs();
+        let period = self.period.load(Ordering::Relaxed);
+        core::sync::atomic::compiler_fence(Ordering::Acquire);
+        let counter = tmr.cval().read().bits() as u16;
+        calc_now(period, counter)
+    }
+
+    fn schedule_wake(&self, at: u64, waker: &Waker) {
+        interrupt::free(|cs| {
+            let mut queue = self.queue.borrow(cs).borrow_mut();
+            if queue.schedule_wake(at, waker) {
+                let mut next = queue.next_expiration(self.now());
+                while !self.set_alarm(cs, next) {
+                    next = queue.next_expiration(self.now());
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,240 @@
+//! SysTick uptime clock and software timers
+//!
+//! [`SysUptime`] counts the time since start as 64-bit [`TimerInstantU64`] that never wraps.
+//! SysTick interrupts once per millisecond (or once per tick below 1 kHz), the exception
+//! handler adds the elapsed ticks and the current SysTick value interpolates in between.
+//!
+//! [`SoftTimers`] runs one-shot and periodic callbacks from the SysTick exception, for
+//! projects without RTIC or Embassy:
+//!
+//! ```rust
+//! static TIMERS: SoftTimers<8, 1_000> = SoftTimers::new();
+//!
+//! let uptime = cp.SYST.uptime_ms(&clocks);
+//! TIMERS.start_periodic(500.millis(), toggle_led).unwrap();
+//!
+//! #[exception]
+//! fn SysTick() {
+//!     SysUptime::<1_000>::on_interrupt();
+//!     TIMERS.poll();
+//! }
+//! ```
+//!
+//! Callbacks run in the SysTick exception, so they must be short.
+
+use super::Timer;
+use core::cell::{Cell, RefCell};
+use core::sync::atomic::{AtomicU32, Ordering};
+use cortex_m::interrupt::{self, Mutex};
+use cortex_m::peripheral::{SCB, SYST};
+use fugit::{TimerDurationU32, TimerInstantU64};
+
+/// Ticks counted up to the last SysTick exception
+static ELAPSED: Mutex<Cell<u64>> = Mutex::new(Cell::new(0));
+/// Ticks per SysTick exception
+static TICKS_PER_IRQ: AtomicU32 = AtomicU32::new(0);
+/// Core clock cycles per tick
+static CYCLES_PER_TICK: AtomicU32 = AtomicU32::new(1);
+/// `FREQ` of the running uptime clock, `0` before the start
+static STARTED_FREQ: AtomicU32 = AtomicU32::new(0);
+
+/// 64-bit uptime clock with `FREQ` ticks per second on SysTick
+///
+/// Only one uptime clock can run, with the `FREQ` it was started with.
+pub struct SysUptime<const FREQ: u32>(Timer<SYST>);
+
+pub type SysUptimeMs = SysUptime<1_000>;
+pub type SysUptimeUs = SysUptime<1_000_000>;
+
+impl Timer<SYST> {
+    /// Creates [SysUptime] with custom precision, `FREQ` must divide the core frequency
+    pub fn uptime<const FREQ: u32>(mut self) -> SysUptime<FREQ> {
+        assert!(self.clk.raw() % FREQ == 0);
+        let cycles = self.clk.raw() / FREQ;
+        let ticks = (FREQ / 1_000).max(1);
+        let rvr = ticks * cycles - 1;
+        assert!(rvr < (1 << 24));
+
+        interrupt::free(|cs| ELAPSED.borrow(cs).set(0));
+        TICKS_PER_IRQ.store(ticks, Ordering::Relaxed);
+        CYCLES_PER_TICK.store(cycles, Ordering::Relaxed);
+        STARTED_FREQ.store(FREQ, Ordering::Relaxed);
+
+        self.tmr.set_reload(rvr);
+        self.tmr.clear_current();
+        self.tmr.enable_interrupt();
+        self.tmr.enable_counter();
+        SysUptime(self)
+    }
+}
+
+impl<const FREQ: u32> SysUptime<FREQ> {
+    /// Time since the uptime clock was started
+    ///
+    /// # Panics
+    ///
+    /// Panics if the uptime clock was not started with this `FREQ`.
+    pub fn now() -> TimerInstantU64<FREQ> {
+        assert!(STARTED_FREQ.load(Ordering::Relaxed) == FREQ);
+        let ticks = interrupt::free(|cs| {
+            let mut elapsed = ELAPSED.borrow(cs).get();
+            let mut cvr = SYST::get_current();
+            // A wrap whose exception didn't run yet
+            if SCB::is_pendst_pending() {
+                cvr = SYST::get_current();
+                elapsed += TICKS_PER_IRQ.load(Ordering::Relaxed) as u64;
+            }
+            let cycles = SYST::get_reload() - cvr;
+            elapsed + (cycles / CYCLES_PER_TICK.load(Ordering::Relaxed)) as u64
+        });
+        TimerInstantU64::from_ticks(ticks)
+    }
+
+    /// Count the elapsed ticks, to be called from the SysTick exception
+    pub fn on_interrupt() {
+        interrupt::free(|cs| {
+            let elapsed = ELAPSED.borrow(cs);
+            elapsed.set(elapsed.get() + TICKS_PER_IRQ.load(Ordering::Relaxed) as u64);
+        });
+    }
+
+    /// Stop the uptime clock and release SysTick
+    pub fn release(mut self) -> Timer<SYST> {
+        STARTED_FREQ.store(0, Ordering::Relaxed);
+        self.0.tmr.disable_interrupt();
+        self.0.tmr.disable_counter();
+        self.0
+    }
+}
+
+/// Handle of a started software timer
+///
+/// A handle refers to one start only, it does not match a later timer that reuses the slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SoftTimerId {
+    slot: usize,
+    generation: u32,
+}
+
+#[derive(Clone, Copy)]
+struct Slot {
+    deadline: u64,
+    /// `0` for one-shot timers
+    period: u64,
+    callback: fn(),
+    generation: u32,
+}
+
+/// Up to `N` software timers driven by [`SysUptime<FREQ>`]
+pub struct SoftTimers<const N: usize, const FREQ: u32> {
+    slots: Mutex<RefCell<[Option<Slot>; N]>>,
+    /// Generation of the next started timer
+    generation: Mutex<Cell<u32>>,
+}
+
+impl<const N: usize, const FREQ: u32> Default for SoftTimers<N, FREQ> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, const FREQ: u32> SoftTimers<N, FREQ> {
+    pub const fn new() -> Self {
+        Self {
+            slots: Mutex::new(RefCell::new([None; N])),
+            generation: Mutex::new(Cell::new(0)),
+        }
+    }
+
+    /// Call `callback` once after `after`
+    ///
+    /// Returns `None` if all timers are in use.
+    pub fn start_once(&self, after: TimerDurationU32<FREQ>, callback: fn()) -> Option<SoftTimerId> {
+        self.start(after, 0, callback)
+    }
+
+    /// Call `callback` every `period`
+    ///
+    /// Returns `None` if all timers are in use.
+    pub fn start_periodic(
+        &self,
+        period: TimerDurationU32<FREQ>,
+        callback: fn(),
+    ) -> Option<SoftTimerId> {
+        self.start(period, period.ticks().max(1) as u64, callback)
+    }
+
+    /// Stop the timer `id`
+    ///
+    /// Does nothing if the timer already expired or was cancelled.
+    pub fn cancel(&self, id: SoftTimerId) {
+        interrupt::free(|cs| {
+            let mut slots = self.slots.borrow(cs).borrow_mut();
+            if Self::matches(&slots[id.slot], id) {
+                slots[id.slot] = None;
+            }
+        });
+    }
+
+    /// Returns `true` while the timer `id` is started
+    pub fn is_active(&self, id: SoftTimerId) -> bool {
+        interrupt::free(|cs| Self::matches(&self.slots.borrow(cs).borrow()[id.slot], id))
+    }
+
+    fn matches(slot: &Option<Slot>, id: SoftTimerId) -> bool {
+        matches!(slot, Some(timer) if timer.generation == id.generation)
+    }
+
+    /// Run the callbacks of the expired timers, to be called from the SysTick exception
+    pub fn poll(&self) {
+        let now = SysUptime::<FREQ>::now().ticks();
+        let mut due: [Option<fn()>; N] = [None; N];
+        interrupt::free(|cs| {
+            for (slot, due) in self.slots.borrow(cs).borrow_mut().iter_mut().zip(&mut due) {
+                let Some(timer) = slot else {
+                    continue;
+                };
+                if timer.deadline > now {
+                    continue;
+                }
+                *due = Some(timer.callback);
+                if timer.period == 0 {
+                    *slot = None;
+                } else {
+                    // Keep the phase, skip periods missed while the exception was blocked
+                    while timer.deadline <= now {
+                        timer.deadline += timer.period;
+                    }
+                }
+            }
+        });
+        // Callbacks may start or cancel timers
+        for callback in due.into_iter().flatten() {
+            callback();
+        }
+    }
+
+    fn start(
+        &self,
+        after: TimerDurationU32<FREQ>,
+        period: u64,
+        callback: fn(),
+    ) -> Option<SoftTimerId> {
+        let deadline = SysUptime::<FREQ>::now().ticks() + after.ticks() as u64;
+        interrupt::free(|cs| {
+            let mut slots = self.slots.borrow(cs).borrow_mut();
+            let slot = slots.iter().position(Option::is_none)?;
+            let next = self.generation.borrow(cs);
+            let generation = next.get();
+            next.set(generation.wrapping_add(1));
+            slots[slot] = Some(Slot {
+                deadline,
+                period,
+                callback,
+                generation,
+            });
+            Some(SoftTimerId { slot, generation })
+        })
+    }
+}
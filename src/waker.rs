@@ -0,0 +1,40 @@
+//! Interrupt-safe waker storage for async drivers
+
+use core::cell::RefCell;
+use core::task::Waker;
+
+use cortex_m::interrupt::{self, Mutex};
+
+/// A single [`Waker`] slot shared between a future and an interrupt handler
+pub(crate) struct WakerCell {
+    waker: Mutex<RefCell<Option<Waker>>>,
+}
+
+impl WakerCell {
+    #[allow(clippy::declare_interior_mutable_const)]
+    pub(crate) const NEW: Self = Self::new();
+
+    pub(crate) const fn new() -> Self {
+        Self {
+            waker: Mutex::new(RefCell::new(None)),
+        }
+    }
+
+    /// Stores `waker`, replacing the previous one unless both wake the same task
+    pub(crate) fn register(&self, waker: &Waker) {
+        interrupt::free(|cs| {
+            let mut slot = self.waker.borrow(cs).borrow_mut();
+            match slot.as_ref() {
+                Some(w) if w.will_wake(waker) => {}
+                _ => *slot = Some(waker.clone()),
+            }
+        });
+    }
+
+    /// Wakes and removes the stored waker, if any
+    pub(crate) fn wake(&self) {
+        if let Some(waker) = interrupt::free(|cs| self.waker.borrow(cs).borrow_mut().take()) {
+            waker.wake();
+        }
+    }
+}